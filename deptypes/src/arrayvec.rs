@@ -0,0 +1,219 @@
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ops::{Deref, DerefMut};
+
+use crate::fin::Fin;
+use crate::int::sub::s_a_minus_s_0_eq_a;
+use crate::int::{ConstUsize, Pred, Succ, Zero};
+use crate::iter::{DIterPair, DLIter, DLIterFamily, FromDLIter};
+use crate::slice::DSlice;
+use crate::term::{Term, Value, ValueEq, ValueLe, ValueLt};
+use crate::transmutable::{coerce, Equiv};
+use crate::type_eq::refl;
+
+/// Fixed-capacity dependent vector stored inline, usable without an allocator
+///
+/// The invariant L <= CAP holds for all values of this type
+#[repr(C)]
+pub struct DArrayVec<T, const CAP: usize, L: Term<Type = usize>> {
+    buf: [MaybeUninit<T>; CAP],
+    len: Value<L>,
+}
+
+impl<T, const CAP: usize, L: Term<Type = usize>> Drop for DArrayVec<T, CAP, L> {
+    fn drop(&mut self) {
+        unsafe {core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.buf.as_mut_ptr() as *mut T, self.len.into_inner()))}
+    }
+}
+
+impl<T, const CAP: usize> DArrayVec<T, CAP, Zero<usize>> {
+    pub fn new() -> Self {
+        // SAFETY: an array of MaybeUninit does not need initialization
+        DArrayVec {buf: unsafe {MaybeUninit::uninit().assume_init()}, len: Zero()}
+    }
+}
+
+impl<T, const CAP: usize> Default for DArrayVec<T, CAP, Zero<usize>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize, L: Term<Type = usize>> DArrayVec<T, CAP, L> {
+    pub fn equiv<L1: Term<Type = usize>>(_: ValueEq<L, L1>) -> Equiv<DArrayVec<T, CAP, L>, DArrayVec<T, CAP, L1>> {
+        unsafe {Equiv::axiom()}
+    }
+
+    pub fn len(&self) -> Value<L> {
+        self.len
+    }
+
+    pub fn capacity(&self) -> Value<ConstUsize<CAP>> {
+        ConstUsize()
+    }
+
+    pub fn len_le_capacity(&self) -> ValueLe<L, ConstUsize<CAP>> {
+        unsafe {ValueLe::axiom()}
+    }
+
+    /// Take the buffer, leaving self to be forgotten
+    unsafe fn take_buf(this: &mut ManuallyDrop<Self>) -> [MaybeUninit<T>; CAP] {
+        core::ptr::read(&this.buf)
+    }
+
+    pub fn push(self, x: T, _lt: ValueLt<L, ConstUsize<CAP>>) -> DArrayVec<T, CAP, Succ<L>> {
+        let mut this = ManuallyDrop::new(self);
+        let len = this.len();
+        // SAFETY: L < CAP
+        unsafe {this.buf.get_unchecked_mut(len.into_inner()).write(x);}
+        DArrayVec {buf: unsafe {Self::take_buf(&mut this)}, len: Succ(len)}
+    }
+
+    pub fn clear(self) -> DArrayVec<T, CAP, Zero<usize>> {
+        let mut this = ManuallyDrop::new(self);
+        unsafe {core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(this.buf.as_mut_ptr() as *mut T, this.len.into_inner()))};
+        DArrayVec {buf: unsafe {Self::take_buf(&mut this)}, len: Zero()}
+    }
+}
+
+impl<T, const CAP: usize, L: Term<Type = usize>> DArrayVec<T, CAP, L> {
+    /// Collect the items of an iterator that fits in the capacity
+    pub fn from_dliter_le<I>(iter: I, len: Value<L>, _le: ValueLe<L, ConstUsize<CAP>>) -> Self
+        where I: DLIter<LengthType = usize, Length = L>, I::Family: DLIterFamily<Item = T> {
+        // SAFETY: an array of MaybeUninit does not need initialization
+        let mut buf: [MaybeUninit<T>; CAP] = unsafe {MaybeUninit::uninit().assume_init()};
+        // the iterator goes first so that it is finished after its last item
+        for (x, slot) in DIterPair::<usize, I::Family>::new(iter, len).zip(buf.iter_mut()) {
+            slot.write(x);
+        }
+        DArrayVec {buf, len}
+    }
+}
+
+impl<T, const CAP: usize> DArrayVec<T, CAP, ConstUsize<CAP>> {
+    pub fn into_array(self) -> [T; CAP] {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: all CAP elements are initialized, and MaybeUninit<T> has the same layout as T
+        unsafe {core::ptr::read(this.buf.as_ptr() as *const [T; CAP])}
+    }
+}
+
+/// Use from_dliter_le for iterators shorter than the capacity
+impl<T, const CAP: usize, I: DLIter<LengthType = usize, Length = ConstUsize<CAP>>> FromDLIter<I> for DArrayVec<T, CAP, ConstUsize<CAP>>
+    where I::Family: DLIterFamily<Item = T> {
+    fn from_dliter(iter: I, len: Value<ConstUsize<CAP>>) -> Self {
+        DArrayVec::from_dliter_le(iter, len, refl().le())
+    }
+}
+
+impl<T, const N: usize, I: DLIter<LengthType = usize, Length = ConstUsize<N>>> FromDLIter<I> for [T; N]
+    where I::Family: DLIterFamily<Item = T> {
+    fn from_dliter(iter: I, len: Value<ConstUsize<N>>) -> Self {
+        DArrayVec::from_dliter_le(iter, len, refl().le()).into_array()
+    }
+}
+
+impl<T, const CAP: usize, L: Term<Type = usize>> DArrayVec<T, CAP, Succ<L>> {
+    pub fn pop(self) -> (DArrayVec<T, CAP, L>, T) {
+        let mut this = ManuallyDrop::new(self);
+        let len = coerce(Pred(this.len()), s_a_minus_s_0_eq_a());
+        // SAFETY: L < Succ<L> <= CAP and the last element is initialized
+        let x = unsafe {this.buf.get_unchecked(len.into_inner()).assume_init_read()};
+        (DArrayVec {buf: unsafe {DArrayVec::take_buf(&mut this)}, len}, x)
+    }
+
+    pub fn swap_remove(self, idx: Fin<L>) -> (DArrayVec<T, CAP, L>, T) {
+        let mut this = ManuallyDrop::new(self);
+        let len = coerce(Pred(this.len()), s_a_minus_s_0_eq_a());
+        // SAFETY: idx < L < Succ<L> <= CAP, so both the hole and the last element are initialized
+        let x = unsafe {
+            let ptr = this.buf.as_mut_ptr() as *mut T;
+            let hole = ptr.add(idx.into_inner());
+            let x = hole.read();
+            core::ptr::copy(ptr.add(len.into_inner()), hole, 1);
+            x
+        };
+        (DArrayVec {buf: unsafe {DArrayVec::take_buf(&mut this)}, len}, x)
+    }
+}
+
+impl<T, const CAP: usize, L: Term<Type = usize>> Deref for DArrayVec<T, CAP, L> {
+    type Target = DSlice<T, L>;
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: the first L elements are initialized
+        unsafe {DSlice::new_ref_unchecked(core::slice::from_raw_parts(self.buf.as_ptr() as *const T, self.len.into_inner()))}
+    }
+}
+
+impl<T, const CAP: usize, L: Term<Type = usize>> DerefMut for DArrayVec<T, CAP, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: the first L elements are initialized
+        unsafe {DSlice::new_mut_unchecked(core::slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut T, self.len.into_inner()))}
+    }
+}
+
+#[test]
+pub fn test_darrayvec() {
+    use crate::term::value_lt;
+
+    let v = DArrayVec::<u32, 3, _>::new();
+    let lt = value_lt(v.len(), v.capacity()).unwrap();
+    let v = v.push(1, lt);
+    let lt = value_lt(v.len(), v.capacity()).unwrap();
+    let v = v.push(2, lt);
+    let lt = value_lt(v.len(), v.capacity()).unwrap();
+    let v = v.push(3, lt);
+    assert!(value_lt(v.len(), v.capacity()).is_none());
+    let idx = Fin::from(coerce(Pred(v.len()), s_a_minus_s_0_eq_a()), 0).unwrap();
+    let (v, x) = v.swap_remove(idx);
+    assert_eq!(x, 1);
+    assert_eq!(v[Fin::from(v.len(), 0).unwrap()], 3);
+    let (v, x) = v.pop();
+    assert_eq!(x, 2);
+    assert_eq!(v.len().into_inner(), 1);
+}
+
+#[test]
+pub fn test_darrayvec_drop() {
+    use core::cell::Cell;
+    use crate::term::value_lt;
+
+    struct D<'a>(&'a Cell<u32>);
+
+    impl<'a> Drop for D<'a> {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let n = Cell::new(0);
+    let v = DArrayVec::<D, 4, _>::new();
+    let lt = value_lt(v.len(), v.capacity()).unwrap();
+    let v = v.push(D(&n), lt);
+    let lt = value_lt(v.len(), v.capacity()).unwrap();
+    let v = v.push(D(&n), lt);
+    let lt = value_lt(v.len(), v.capacity()).unwrap();
+    let v = v.push(D(&n), lt);
+    let (v, x) = v.pop();
+    drop(x);
+    assert_eq!(n.get(), 1);
+    drop(v);
+    assert_eq!(n.get(), 3);
+}
+
+#[test]
+pub fn test_collect_d() {
+    use crate::iter::DLIter;
+    use crate::term::value_le;
+
+    let a = [1, 2, 3];
+    let n = ConstUsize::<3>();
+    let sa: &DSlice<i32, _> = (&a).into();
+    let arr: [i32; 3] = sa.iter().map(|x| x * 10).collect_d(n);
+    assert_eq!(arr, [10, 20, 30]);
+    let le = value_le(n, ConstUsize::<5>()).unwrap();
+    let av = DArrayVec::<i32, 5, _>::from_dliter_le(sa.iter().rev().map(|x| *x), n, le);
+    assert_eq!(av.as_slice(), &[3, 2, 1]);
+    let av: DArrayVec<i32, 3, _> = sa.iter().map(|x| *x).collect_d(n);
+    assert_eq!(av.as_slice(), &[1, 2, 3]);
+}
@@ -0,0 +1,93 @@
+use core::ops::Deref;
+
+use crate::int::uint::UInt;
+use crate::iter::{DLIter, DLIterFamily, FromDLIter};
+use crate::slice::DSlice;
+use crate::term::{Term, Value, ValueEq};
+use crate::transmutable::{Equiv, Transm};
+use crate::vec::DVec;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Owned immutable slice whose length is L, without the spare capacity of a DVec
+#[repr(C)]
+pub struct DBox<T, L: Term>(Box<[T]>, Value<L>);
+
+impl<T, L: Term> DBox<T, L> {
+    pub fn equiv<L1: Term>(_: ValueEq<L, L1>) -> Equiv<DBox<T, L>, DBox<T, L1>> {
+        unsafe {Equiv::axiom()}
+    }
+
+    pub fn transm<T1, L1: Term>(_: Transm<T, T1>, _: ValueEq<L, L1>) -> Transm<DBox<T, L>, DBox<T1, L1>> {
+        unsafe {Transm::axiom()}
+    }
+
+    /// # Safety
+    ///
+    /// x must have length len
+    pub unsafe fn new_unchecked(x: Box<[T]>, len: Value<L>) -> Self {
+        DBox(x, len)
+    }
+
+    pub fn into_inner(self) -> (Box<[T]>, Value<L>) {
+        (self.0, self.1)
+    }
+
+    pub fn len(&self) -> Value<L>
+        where L::Type: Clone {
+        self.1.clone()
+    }
+}
+
+impl<T, L: Term> DBox<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    pub fn into_dvec(self) -> DVec<T, L> {
+        unsafe {DVec::new_unchecked(Vec::from(self.0), self.1)}
+    }
+}
+
+impl<T, L: Term> DVec<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    /// Convert into a DBox, dropping any spare capacity
+    pub fn into_boxed(self) -> DBox<T, L> {
+        let len = self.len();
+        DBox(self.into_vec().into_boxed_slice(), len)
+    }
+}
+
+impl<T, L: Term> From<DVec<T, L>> for DBox<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    fn from(x: DVec<T, L>) -> Self {
+        x.into_boxed()
+    }
+}
+
+impl<T, L: Term> From<DBox<T, L>> for DVec<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    fn from(x: DBox<T, L>) -> Self {
+        x.into_dvec()
+    }
+}
+
+impl<T, I: DLIter> FromDLIter<I> for DBox<T, I::Length>
+    where usize: From<I::LengthType>, I::Family: DLIterFamily<Item = T> {
+    fn from_dliter(iter: I, len: Value<I::Length>) -> Self {
+        DVec::from_dliter(iter, len).into_boxed()
+    }
+}
+
+impl<T: Clone, L: Term> Clone for DBox<T, L>
+    where L::Type: Clone {
+    fn clone(&self) -> Self {
+        DBox(self.0.clone(), self.1.clone())
+    }
+}
+
+impl<T, L: Term> Deref for DBox<T, L> {
+    type Target = DSlice<T, L>;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe {DSlice::new_ref_unchecked(&self.0)}
+    }
+}
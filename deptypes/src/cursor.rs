@@ -0,0 +1,105 @@
+use crate::int::{ConstUsize, Zero};
+use crate::ops::{Add, Sub};
+use crate::slice::{DSlice, DSliceRef};
+use crate::term::{Term, Value, ValueGt, ValueLe};
+
+/// Cursor over a byte DSlice of length L, having consumed the first P bytes
+///
+/// The invariant P <= L holds for all values of this type
+pub struct DCursor<'x, L: Term<Type = usize>, P: Term<Type = usize>> {
+    s: &'x DSlice<u8, L>,
+    pos: Value<P>,
+}
+
+/// Result of DCursor::check: whether N more bytes are available after P
+pub type DCursorCheck<L, P, N> = Result<ValueLe<Add<P, N>, L>, ValueGt<Add<P, N>, L>>;
+
+/// Result of reading an X from N bytes: the advanced cursor and the X
+pub type DCursorRead<'x, L, P, N, X> = (DCursor<'x, L, Add<P, N>>, X);
+
+/// Result of reading an X from N bytes if they are available
+pub type DCursorTryRead<'x, L, P, N, X> = Result<DCursorRead<'x, L, P, N, X>, ValueGt<Add<P, N>, L>>;
+
+impl<'x, L: Term<Type = usize>> DCursor<'x, L, Zero<usize>> {
+    pub fn new(s: &'x DSlice<u8, L>) -> Self {
+        DCursor {s, pos: Zero()}
+    }
+}
+
+impl<'x, L: Term<Type = usize>, P: Term<Type = usize>> DCursor<'x, L, P> {
+    pub fn pos(&self) -> Value<P> {
+        self.pos
+    }
+
+    pub fn remaining(&self) -> Value<Sub<L, P>> {
+        Sub(self.s.len(), self.pos)
+    }
+
+    /// Check that n more bytes are available
+    pub fn check<N: Term<Type = usize>>(&self, n: Value<N>) -> DCursorCheck<L, P, N> {
+        // comparing with L - P rather than computing P + N, which could overflow
+        if n.into_inner() <= self.remaining().into_inner() {
+            Ok(unsafe {ValueLe::axiom()})
+        } else {
+            Err(unsafe {ValueGt::axiom()})
+        }
+    }
+
+    /// Consume the next n bytes
+    pub fn take<N: Term<Type = usize>>(self, n: Value<N>, _le: ValueLe<Add<P, N>, L>) -> DCursorRead<'x, L, P, N, DSliceRef<'x, u8, N>> {
+        let pos = self.pos.into_inner();
+        // SAFETY: P + N <= L
+        let s = unsafe {DSlice::new_ref_unchecked(self.s.as_slice().get_unchecked(pos..pos + n.into_inner()))};
+        (DCursor {s: self.s, pos: Add(self.pos, n)}, DSliceRef::new(s))
+    }
+
+    /// Consume the next len_field bytes, checking that they are available
+    pub fn take_prefixed<N: Term<Type = usize>>(self, len_field: Value<N>) -> DCursorTryRead<'x, L, P, N, DSliceRef<'x, u8, N>> {
+        let le = self.check(len_field)?;
+        Ok(self.take(len_field, le))
+    }
+
+    pub fn read_array<const N: usize>(self, le: ValueLe<Add<P, ConstUsize<N>>, L>) -> DCursorRead<'x, L, P, ConstUsize<N>, [u8; N]> {
+        let (cursor, s) = self.take(ConstUsize(), le);
+        let mut a = [0; N];
+        <&mut DSlice<u8, ConstUsize<N>>>::from(&mut a).copy_from_dslice(&s);
+        (cursor, a)
+    }
+
+    pub fn read_u32_le(self, le: ValueLe<Add<P, ConstUsize<4>>, L>) -> DCursorRead<'x, L, P, ConstUsize<4>, u32> {
+        let (cursor, a) = self.read_array(le);
+        (cursor, u32::from_le_bytes(a))
+    }
+
+    pub fn try_read_u32_le(self) -> DCursorTryRead<'x, L, P, ConstUsize<4>, u32> {
+        let le = self.check(ConstUsize())?;
+        Ok(self.read_u32_le(le))
+    }
+
+    /// The bytes that have not been consumed
+    pub fn rest(self) -> DSliceRef<'x, u8, Sub<L, P>> {
+        let pos = self.pos.into_inner();
+        // SAFETY: P <= L
+        DSliceRef::new(unsafe {DSlice::new_ref_unchecked(self.s.as_slice().get_unchecked(pos..))})
+    }
+}
+
+#[test]
+pub fn test_cursor() {
+    use crate::guard::make_guard;
+    use crate::var::Var;
+
+    let data = [7, 0, 0, 0, 2, 0, 0, 0, b'h', b'i', 0xff];
+    make_guard!(g);
+    let (s, _) = DSlice::new_ref(g, &data);
+    let c = DCursor::new(s);
+    let (c, tag) = c.try_read_u32_le().ok().unwrap();
+    assert_eq!(tag, 7);
+    let (c, len) = c.try_read_u32_le().ok().unwrap();
+    make_guard!(g);
+    let (c, v) = c.take_prefixed(Var(g, len as usize)).ok().unwrap();
+    assert_eq!(v.as_slice(), b"hi");
+    assert_eq!(c.pos().into_inner(), 10);
+    assert!(c.check(ConstUsize::<2>()).is_err());
+    assert!(c.try_read_u32_le().is_err());
+}
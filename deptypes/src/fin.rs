@@ -2,7 +2,9 @@ use core::ops::AddAssign;
 
 use num_traits::CheckedAdd;
 
-use crate::term::{Term, Value, ValueEq, ValueLe};
+use crate::guard::Guard;
+use crate::term::{Term, Value, ValueEq, ValueLe, ValueLt};
+use crate::var::{Erasure, Var};
 use crate::transmutable::{Transm, Equiv};
 use crate::ops::{Add, ConstOps};
 
@@ -60,6 +62,16 @@ impl<A: Term> Fin<A> {
     pub fn transm<B: Term<Type = A::Type>>(_: ValueLe<A, B>) -> Transm<Fin<A>, Fin<B>> {
         unsafe {Transm::axiom()}
     }
+
+    pub fn from_lt<X: Term<Type = A::Type>>(x: Value<X>, _: ValueLt<X, A>) -> Fin<A> {
+        Fin(x.into_inner())
+    }
+
+    /// Name the index with a Var, along with the proof that it is less than A
+    pub fn into_lt<'a>(self, guard: Guard<'a>) -> (Value<Erasure<'a, A>>, ValueLt<Erasure<'a, A>, A>) {
+        // SAFETY: the value of a Fin<A> is less than A
+        (Var(guard, self.0), unsafe {ValueLt::axiom()})
+    }
 }
 
 #[repr(C)]
@@ -1,11 +1,12 @@
 pub mod add;
 pub mod sub;
 pub mod neg;
+pub mod mul;
 pub mod uint;
 
 use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub};
 
-use crate::ops::{ConstOps, Sub};
+use crate::ops::{Add, ConstOps, Div, Rem, Sub};
 use crate::term::{value_cmp, Def, Term, Value, ValueLt};
 use crate::type_eq::{refl, TypeOrdering};
 use crate::var::Var;
@@ -91,6 +92,16 @@ pub fn Pred<A: Term>(a: Value<A>) -> Value<Pred<A>>
     Sub(a, One())
 }
 
+/// Rounded-up quotient, computed as a / b + (1 - (b - a % b) / b) so that no intermediate value overflows
+pub type DivCeil<A, B> = Add<Div<A, B>, Sub<One<<A as Term>::Type>, Div<Sub<B, Rem<A, B>>, B>>>;
+
+#[allow(non_snake_case)]
+pub fn DivCeil<A: Term, B: Term<Type = A::Type>>(a: Value<A>, b: Value<B>) -> Value<DivCeil<A, B>>
+    where A::Type: Int {
+    let rem = Rem(a.clone(), b.clone());
+    Add(Div(a, b.clone()), Sub(One(), Div(Sub(b.clone(), rem), b)))
+}
+
 pub fn int_pred_or_succ<'a, A: Term>(
     v: Value<A>,
 ) -> Result<
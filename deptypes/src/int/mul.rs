@@ -0,0 +1,91 @@
+use crate::ops::{Add, Mul};
+use crate::term::{Term, ValueEq, ValueLt};
+use crate::type_eq::refl;
+
+use super::add::add_0_a_eq_a;
+use super::uint::UInt;
+use super::{Int, One, Succ, Zero};
+
+/// Axiom (definition of multiplication): a * 0 = 0
+pub fn a_times_0_eq_0<N, A: Term<Type = N>>(
+    ) -> ValueEq<Mul<A, Zero<N>>, Zero<N>>
+    where N: Int
+{
+    // SAFETY: this is true for mathematical integers and thus for all types that implement Int by the requirements of Int
+    unsafe {ValueEq::axiom()}
+}
+
+/// Axiom (definition of multiplication): a * S(b) = a * b + a
+pub fn a_times_s_b_eq_a_times_b_plus_a<N, A: Term<Type = N>, B: Term<Type = N>>(
+    ) -> ValueEq<Mul<A, Succ<B>>, Add<Mul<A, B>, A>>
+    where N: Int
+{
+    // SAFETY: this is true for mathematical integers and thus for all types that implement Int by the requirements of Int
+    unsafe {ValueEq::axiom()}
+}
+
+/// Axiom: a * b = b * a
+pub fn mul_commutative<N, A: Term<Type = N>, B: Term<Type = N>>(
+    ) -> ValueEq<Mul<A, B>, Mul<B, A>>
+    where N: Int
+{
+    // SAFETY: this is true for mathematical integers and thus for all types that implement Int by the requirements of Int
+    unsafe {ValueEq::axiom()}
+}
+
+/// Axiom: a * (b * c) = (a * b) * c
+pub fn mul_associative<N, A: Term<Type = N>, B: Term<Type = N>, C: Term<Type = N>>(
+    ) -> ValueEq<Mul<A, Mul<B, C>>, Mul<Mul<A, B>, C>>
+    where N: Int
+{
+    // SAFETY: this is true for mathematical integers and thus for all types that implement Int by the requirements of Int
+    unsafe {ValueEq::axiom()}
+}
+
+/// Axiom: a < b && c < d => a * d + c < b * d if all are UInt
+///
+/// This is what makes row-major indexing of a b * d buffer by (a, c) in bounds
+pub fn mul_add_lt<N, A: Term<Type = N>, B: Term<Type = N>, C: Term<Type = N>, D: Term<Type = N>>(
+    _a_lt_b: ValueLt<A, B>,
+    _c_lt_d: ValueLt<C, D>
+    ) -> ValueLt<Add<Mul<A, D>, C>, Mul<B, D>>
+    where N: UInt
+{
+    // SAFETY: for mathematical non-negative integers a * d + c <= (b - 1) * d + (d - 1) = b * d - 1, and thus for all types that implement UInt by the requirements of UInt
+    // this needs UInt rather than Int, since it fails for negative integers, e.g. a = -3, b = -1, c = -3, d = -2 gives 3 < 2
+    unsafe {ValueLt::axiom()}
+}
+
+/// Theorem: 0 * a = 0
+pub fn mul_0_a_eq_0<N, A: Term<Type = N>>(
+    ) -> ValueEq<Mul<Zero<N>, A>, Zero<N>>
+    where N: Int
+{
+    mul_commutative() + a_times_0_eq_0()
+}
+
+/// Theorem: a * S(0) = a
+pub fn a_times_1_eq_a<N, A: Term<Type = N>>(
+    ) -> ValueEq<Mul<A, One<N>>, A>
+    where N: Int
+{
+    // a * S(0) == a * 0 + a == 0 + a == a
+    a_times_s_b_eq_a_times_b_plus_a() + Add::eq(a_times_0_eq_0(), refl()) + add_0_a_eq_a()
+}
+
+/// Theorem: S(0) * a = a
+pub fn mul_1_a_eq_a<N, A: Term<Type = N>>(
+    ) -> ValueEq<Mul<One<N>, A>, A>
+    where N: Int
+{
+    mul_commutative() + a_times_1_eq_a()
+}
+
+/// Theorem: S(a) * b = a * b + b
+pub fn s_a_times_b_eq_a_times_b_plus_b<N, A: Term<Type = N>, B: Term<Type = N>>(
+    ) -> ValueEq<Mul<Succ<A>, B>, Add<Mul<A, B>, B>>
+    where N: Int
+{
+    // S(a) * b == b * S(a) == b * a + b == a * b + b
+    mul_commutative() + a_times_s_b_eq_a_times_b_plus_a() + Add::eq(mul_commutative(), refl())
+}
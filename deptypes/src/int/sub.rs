@@ -184,3 +184,12 @@ pub fn sub_eq_to_right_eq<N, A: Term<Type = N>, B: Term<Type = N>, C: Term<Type
     // c == a - (a - c) == b - (b - d) == d
     -a_minus_sub_a_b_eq_b() + Sub::eq(a_eq_b, eq) + a_minus_sub_a_b_eq_b()
 }
+
+/// Theorem: b + (a - b) = a
+pub fn b_plus_sub_a_b_eq_a<N, A: Term<Type = N>, B: Term<Type = N>>(
+) -> ValueEq<Add<B, Sub<A, B>>, A>
+where N: Int
+{
+    // b + (a - b) == (a - b) + b == a
+    add_commutative() + a_minus_b_plus_b_eq_a()
+}
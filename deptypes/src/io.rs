@@ -0,0 +1,72 @@
+use std::io::{self, BufRead, Read, Write};
+
+use crate::guard::make_guard;
+use crate::int::uint::UInt;
+use crate::pair::DPair;
+use crate::slice::DSlice;
+use crate::term::{Term, Value};
+use crate::vec::{DVec, DVecFamily};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Read exactly len bytes
+pub fn read_exact_dvec<R: Read + ?Sized, L: Term>(reader: &mut R, len: Value<L>) -> io::Result<DVec<u8, L>>
+    where usize: From<L::Type>, L::Type: UInt {
+    let mut buf = vec![0; usize::from(len.clone().into_inner())];
+    reader.read_exact(&mut buf)?;
+    Ok(unsafe {DVec::new_unchecked(buf, len)})
+}
+
+/// Write all bytes of s
+///
+/// Unlike read_exact_dvec, this takes no Value<L>: a &DSlice carries its length in the fat pointer
+pub fn write_dslice<W: Write + ?Sized, L: Term>(writer: &mut W, s: &DSlice<u8, L>) -> io::Result<()> {
+    writer.write_all(s.as_slice())
+}
+
+fn into_dpair(buf: Vec<u8>) -> DPair<usize, DVecFamily<u8>> {
+    make_guard!(g);
+    let v = DVec::from(g, buf);
+    DPair::new(v.len(), v)
+}
+
+/// Read bytes until delim or EOF, including delim if found
+pub fn read_until_dvec<R: BufRead + ?Sized>(reader: &mut R, delim: u8) -> io::Result<DPair<usize, DVecFamily<u8>>> {
+    let mut buf = Vec::new();
+    reader.read_until(delim, &mut buf)?;
+    Ok(into_dpair(buf))
+}
+
+/// Read a little-endian u32 length followed by that many bytes
+pub fn read_u32_le_prefixed<R: Read + ?Sized>(reader: &mut R) -> io::Result<DPair<usize, DVecFamily<u8>>> {
+    let mut prefix = [0; 4];
+    reader.read_exact(&mut prefix)?;
+    let len = u32::from_le_bytes(prefix) as usize;
+    let mut buf = Vec::new();
+    reader.take(len as u64).read_to_end(&mut buf)?;
+    if buf.len() != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(into_dpair(buf))
+}
+
+#[test]
+pub fn test_io() {
+    let mut input: &[u8] = b"\x03\x00\x00\x00abcdef\nxy";
+    let pair = read_u32_le_prefixed(&mut input).unwrap();
+    make_guard!(g);
+    let (len, v) = pair.into_inner(g);
+    assert_eq!(len.into_inner(), 3);
+
+    let mut out = Vec::new();
+    write_dslice(&mut out, &v).unwrap();
+    assert_eq!(out, b"abc");
+
+    let rest = read_exact_dvec(&mut input, crate::int::ConstUsize::<2>()).unwrap();
+    assert_eq!(rest.into_vec(), b"de");
+    make_guard!(g);
+    let (len, _) = read_until_dvec(&mut input, b'\n').unwrap().into_inner(g);
+    assert_eq!(len.into_inner(), 2);
+    assert!(read_u32_le_prefixed(&mut input).is_err());
+}
@@ -1,11 +1,13 @@
 use generativity::make_guard;
 
+use crate::fin::Fin;
 use crate::guard::Guard;
 use crate::kinds::Term2S;
-use crate::int::sub::s_a_minus_s_0_eq_a;
+use crate::int::add::{add_0_a_eq_a, s_a_plus_b_eq_s_add_a_b};
+use crate::int::sub::{b_plus_sub_a_b_eq_a, s_a_minus_s_0_eq_a};
 use crate::int::uint::{uint_as_succ, UInt};
 use crate::loops::repeat_to_zero;
-use crate::ops::Sub;
+use crate::ops::{Add, Sub};
 use crate::pair::DPair;
 use crate::transmutable::{coerce, Equiv};
 use crate::type_eq::refl;
@@ -13,9 +15,9 @@ use crate::unreachable::Unreachable;
 use core::marker::PhantomData;
 use core::mem::ManuallyDrop;
 
-use crate::term::{Term, Value, ValueEq};
+use crate::term::{Term, Value, ValueEq, ValueGt, ValueLe};
 use crate::var::Var;
-use crate::int::{Pred, Succ, Zero};
+use crate::int::{DivCeil, Pred, Succ, Zero};
 
 /// SAFETY: You must either implement equiv yourself, or Iter<L> must be transmutable to/from Iter<L2> where L and L2 are value-eq. This means it should be #[repr(C)] or #[repr(transparent)] or ZST
 pub unsafe trait DLIterFamily {
@@ -35,24 +37,39 @@ pub unsafe trait DLIterFamily {
     }
 }
 
+/// Family of iterators that can also yield items from the back
+pub trait DLIterFamilyBack: DLIterFamily {
+    fn next_back<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item);
+}
+
+/// Maps a length L to the iterator of family F with length L
 #[allow(non_camel_case_types)]
-struct DLIterFamily_Iter<F: DLIterFamily>(PhantomData<F>);
+pub struct DLIterFamily_Iter<F: DLIterFamily>(PhantomData<F>);
 
 /// SAFETY: guaranteed by the safety requirements on DIterNoLenFamily
 unsafe impl<F: DLIterFamily> Term2S<F::LengthType> for DLIterFamily_Iter<F> {
     type Type<L: Term<Type = F::LengthType>> = F::Iter<L>;
 }
 
+#[allow(non_camel_case_types)]
+struct DLIterFamily_IterAdd<F: DLIterFamily, E>(PhantomData<(F, E)>);
+
+/// SAFETY: guaranteed by the safety requirements on DIterNoLenFamily, since Add preserves value-equivalence
+unsafe impl<F: DLIterFamily, E: Term<Type = F::LengthType>> Term2S<F::LengthType> for DLIterFamily_IterAdd<F, E> {
+    type Type<L: Term<Type = F::LengthType>> = F::Iter<Add<L, E>>;
+}
+
 /// SAFETY: must be transmutable to/from Generic<L2> where L2 is a value-equivalent term
 pub trait DLIter {
     type LengthType: UInt;
     type Family: DLIterFamily<LengthType = Self::LengthType, Iter<Self::Length> = Self>;
     type Length: Term<Type = Self::LengthType>;
 
-    fn next(self, value: Value<Self::Length>) -> Result<
-        (<Self::Family as DLIterFamily>::Iter<Pred<Self::Length>>, <Self::Family as DLIterFamily>::Item),
-        ValueEq<Self::Length, Zero::<<Self::Length as Term>::Type>>
-    > where Self: Sized
+    fn next(self, value: Value<Self::Length>) -> DLIterNext<Self>
+        where Self: Sized
     {
         make_guard!(g);
         match uint_as_succ(g, value) {
@@ -78,6 +95,493 @@ pub trait DLIter {
         });
         Self::Family::finish(iter);
     }
+
+    fn next_back(self, value: Value<Self::Length>) -> DLIterNext<Self>
+        where Self: Sized, Self::Family: DLIterFamilyBack
+    {
+        make_guard!(g);
+        match uint_as_succ(g, value) {
+            Ok((pred, eq)) => {
+                let iter = coerce(self, Self::Family::equiv(eq));
+                let (iter, x) = <Self::Family as DLIterFamilyBack>::next_back(iter, pred);
+                let iter = coerce(iter, Self::Family::equiv(-s_a_minus_s_0_eq_a() - Sub::eq(eq, refl())));
+                Ok((iter, x))
+            },
+            Err(is_zero) => {
+                let iter = coerce(self, Self::Family::equiv(is_zero));
+                Self::Family::finish(iter);
+                Err(is_zero)
+            }
+        }
+    }
+
+    /// Iterate in reverse order
+    fn rev(self) -> DRev<Self>
+        where Self: Sized, Self::Family: DLIterFamilyBack {
+        DRev(self)
+    }
+
+    /// Yield pairs of the index and the item, where the index is below the current length
+    fn enumerate(self) -> DEnumerate<Self, Self::Length>
+        where Self: Sized {
+        DEnumerate(self, num_traits::Zero::zero(), PhantomData)
+    }
+
+    fn map<U, G: FnMut(<Self::Family as DLIterFamily>::Item) -> U>(self, f: G) -> DMap<Self, G, U>
+        where Self: Sized {
+        DMap(self, f, PhantomData)
+    }
+
+    /// Call f on a reference to each item before yielding it
+    fn inspect<G: FnMut(&<Self::Family as DLIterFamily>::Item)>(self, f: G) -> DInspect<Self, G>
+        where Self: Sized {
+        DInspect(self, f)
+    }
+
+    /// Yield pairs of items of self and other, which have the same length
+    fn zip<B: DLIter<LengthType = Self::LengthType, Length = Self::Length>>(self, other: B) -> DZip<Self, B>
+        where Self: Sized {
+        DZip(self, other)
+    }
+
+    /// Yield the items of self followed by the items of other
+    fn chain<B: DLIter<LengthType = Self::LengthType>>(self, len: Value<Self::Length>, other: B, other_len: Value<B::Length>)
+        -> DChainIter<Self, B>
+        where Self: Sized, B::Family: DLIterFamily<Item = <Self::Family as DLIterFamily>::Item> {
+        DChain(Some(DPair::new(len, self)), DPair::new(other_len, other), PhantomData)
+    }
+
+    /// Yield only the first n items, dropping the rest
+    fn take<N: Term<Type = Self::LengthType>>(self, len: Value<Self::Length>, n: Value<N>, _le: ValueLe<N, Self::Length>)
+        -> DTakeIter<Self, N>
+        where Self: Sized {
+        let iter = coerce(self, Self::Family::equiv(-b_plus_sub_a_b_eq_a()));
+        DTake(iter, Sub(len, n))
+    }
+
+    /// Drop the first n items and return the iterator over the rest
+    fn skip<N: Term<Type = Self::LengthType>>(self, len: Value<Self::Length>, n: Value<N>, _le: ValueLe<N, Self::Length>)
+        -> DSkipIter<Self, N>
+        where Self: Sized {
+        let rest = Sub(len, n.clone());
+        let iter = coerce(self, Self::Family::equiv(-b_plus_sub_a_b_eq_a()));
+        let iter = repeat_to_zero::<DLIterFamily_IterAdd<Self::Family, _>, _, _>(n, iter, |pred_n, iter| {
+            let iter = coerce(iter, Self::Family::equiv(s_a_plus_b_eq_s_add_a_b()));
+            let (iter, _) = <Self::Family as DLIterFamily>::next(iter, Add(pred_n, rest.clone()));
+            iter
+        });
+        coerce(iter, Self::Family::equiv(add_0_a_eq_a()))
+    }
+
+    /// Yield every k-th item, starting with the first
+    fn step_by<K: Term<Type = Self::LengthType>>(self, len: Value<Self::Length>, k: Value<K>, _gt: ValueGt<K, Zero<Self::LengthType>>)
+        -> DStepByIter<Self, K>
+        where Self: Sized {
+        DStepBy(DPair::new(len, self), k.into_inner(), PhantomData)
+    }
+
+    /// Collect all items into a container whose length is the length of the iterator
+    fn collect_d<C: FromDLIter<Self>>(self, len: Value<Self::Length>) -> C
+        where Self: Sized {
+        C::from_dliter(self, len)
+    }
+}
+
+/// Conversion from a DLIter of known length, analogous to FromIterator
+pub trait FromDLIter<I: DLIter>: Sized {
+    fn from_dliter(iter: I, len: Value<I::Length>) -> Self;
+}
+
+macro_rules! impl_adapter_family {
+    ($S:ident $F:ident [$($gen:tt)*] [$($fgen:tt)*] [$($where:tt)*] [$($extra:tt)*] $Item:ty, |$f:ident, $x:ident| $map:expr) => {
+        unsafe impl<F: DLIterFamily, $($gen)*> DLIterFamily for $F<F, $($fgen)*>
+            where $($where)* {
+            type LengthType = F::LengthType;
+            type Iter<L: Term<Type = Self::LengthType>> = $S<F::Iter<L>, $($fgen)*>;
+            type Item = $Item;
+
+            fn next<L: Term<Type = Self::LengthType>>(
+                iter: Self::Iter<Succ<L>>,
+                value: Value<L>
+            ) -> (Self::Iter<L>, Self::Item) {
+                let $S(iter, mut $f, ..) = iter;
+                let (iter, $x) = F::next(iter, value);
+                let x = $map;
+                ($S(iter, $f $($extra)*), x)
+            }
+
+            fn finish(iter: Self::Iter<Zero<Self::LengthType>>) {
+                F::finish(iter.0)
+            }
+        }
+
+        impl<F: DLIterFamilyBack, $($gen)*> DLIterFamilyBack for $F<F, $($fgen)*>
+            where $($where)* {
+            fn next_back<L: Term<Type = Self::LengthType>>(
+                iter: Self::Iter<Succ<L>>,
+                value: Value<L>
+            ) -> (Self::Iter<L>, Self::Item) {
+                let $S(iter, mut $f, ..) = iter;
+                let (iter, $x) = F::next_back(iter, value);
+                let x = $map;
+                ($S(iter, $f $($extra)*), x)
+            }
+        }
+
+        impl<I: DLIter, $($gen)*> DLIter for $S<I, $($fgen)*>
+            where $F<I::Family, $($fgen)*>: DLIterFamily<LengthType = I::LengthType, Iter<I::Length> = Self> {
+            type LengthType = I::LengthType;
+            type Length = I::Length;
+            type Family = $F<I::Family, $($fgen)*>;
+
+            fn drop(self, len: Value<I::Length>) {
+                self.0.drop(len)
+            }
+        }
+    }
+}
+
+/// Iterator adapter that yields the results of calling G on the items of I
+#[repr(C)]
+pub struct DMap<I, G, U>(I, G, PhantomData<fn() -> U>);
+
+pub struct DMapFamily<F, G, U>(PhantomData<fn(F, G) -> U>);
+
+impl_adapter_family! {DMap DMapFamily [G, U] [G, U] [G: FnMut(F::Item) -> U] [, PhantomData] U, |f, x| f(x)}
+
+/// Iterator adapter that calls G on a reference to each item of I before yielding it
+#[repr(C)]
+pub struct DInspect<I, G>(I, G);
+
+pub struct DInspectFamily<F, G>(PhantomData<(F, G)>);
+
+impl_adapter_family! {DInspect DInspectFamily [G] [G] [G: FnMut(&F::Item)] [] F::Item, |f, x| {f(&x); x}}
+
+/// Iterator adapter that yields pairs of items of A and B, which have the same length
+#[repr(C)]
+pub struct DZip<A, B>(A, B);
+
+pub struct DZipFamily<FA, FB>(PhantomData<(FA, FB)>);
+
+unsafe impl<FA: DLIterFamily, FB: DLIterFamily<LengthType = FA::LengthType>> DLIterFamily for DZipFamily<FA, FB> {
+    type LengthType = FA::LengthType;
+    type Iter<L: Term<Type = Self::LengthType>> = DZip<FA::Iter<L>, FB::Iter<L>>;
+    type Item = (FA::Item, FB::Item);
+
+    fn next<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let (a, x) = FA::next(iter.0, value.clone());
+        let (b, y) = FB::next(iter.1, value);
+        (DZip(a, b), (x, y))
+    }
+
+    fn finish(iter: Self::Iter<Zero<Self::LengthType>>) {
+        FA::finish(iter.0);
+        FB::finish(iter.1);
+    }
+}
+
+impl<FA: DLIterFamilyBack, FB: DLIterFamilyBack<LengthType = FA::LengthType>> DLIterFamilyBack for DZipFamily<FA, FB> {
+    fn next_back<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let (a, x) = FA::next_back(iter.0, value.clone());
+        let (b, y) = FB::next_back(iter.1, value);
+        (DZip(a, b), (x, y))
+    }
+}
+
+impl<A: DLIter, B: DLIter<LengthType = A::LengthType, Length = A::Length>> DLIter for DZip<A, B> {
+    type LengthType = A::LengthType;
+    type Length = A::Length;
+    type Family = DZipFamily<A::Family, B::Family>;
+
+    fn drop(self, len: Value<A::Length>) {
+        self.0.drop(len.clone());
+        self.1.drop(len);
+    }
+}
+
+/// Iterator adapter that yields the items of an iterator of family FA followed by those of one of family FB
+///
+/// The lengths of the two iterators add up to L
+#[repr(C)]
+pub struct DChain<FA: DLIterFamily, FB: DLIterFamily<LengthType = FA::LengthType>, L>(
+    Option<DPair<FA::LengthType, DLIterFamily_Iter<FA>>>,
+    DPair<FA::LengthType, DLIterFamily_Iter<FB>>,
+    PhantomData<Value<L>>
+) where L: Term<Type = FA::LengthType>;
+
+pub struct DChainFamily<FA, FB>(PhantomData<(FA, FB)>);
+
+unsafe impl<FA: DLIterFamily, FB: DLIterFamily<LengthType = FA::LengthType, Item = FA::Item>> DLIterFamily for DChainFamily<FA, FB> {
+    type LengthType = FA::LengthType;
+    type Iter<L: Term<Type = Self::LengthType>> = DChain<FA, FB, L>;
+    type Item = FA::Item;
+
+    fn next<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        _value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let DChain(a, b, _) = iter;
+        if let Some(a) = a {
+            make_guard!(g);
+            let (len, a) = a.into_inner(g);
+            let res = a.next(len.clone());
+            if let Ok((a, x)) = res {
+                return (DChain(Some(DPair::new(Pred(len), a)), b, PhantomData), x);
+            }
+        }
+        make_guard!(g);
+        let (len, b) = b.into_inner(g);
+        let res = b.next(len.clone());
+        match res {
+            Ok((b, x)) => (DChain(None, DPair::new(Pred(len), b), PhantomData), x),
+            // SAFETY: the lengths add up to L + 1, and the first iterator is empty
+            Err(_) => unsafe {core::hint::unreachable_unchecked()}
+        }
+    }
+
+    fn finish(iter: Self::Iter<Zero<Self::LengthType>>) {
+        DLIter::drop(iter, Zero())
+    }
+}
+
+impl<FA: DLIterFamily, FB: DLIterFamily<LengthType = FA::LengthType, Item = FA::Item>, L: Term<Type = FA::LengthType>> DLIter for DChain<FA, FB, L> {
+    type LengthType = FA::LengthType;
+    type Length = L;
+    type Family = DChainFamily<FA, FB>;
+
+    fn drop(self, _len: Value<L>) {
+        if let Some(a) = self.0 {
+            make_guard!(g);
+            let (len, a) = a.into_inner(g);
+            a.drop(len);
+        }
+        make_guard!(g);
+        let (len, b) = self.1.into_inner(g);
+        b.drop(len);
+    }
+}
+
+/// Iterator adapter that yields the first L items of an iterator of family F that has E further items
+///
+/// The E remaining items of the inner iterator are dropped when the adapter is finished or dropped
+#[repr(C)]
+pub struct DTake<F: DLIterFamily, E: Term<Type = F::LengthType>, L: Term<Type = F::LengthType>>(F::Iter<Add<L, E>>, Value<E>);
+
+pub struct DTakeFamily<F, E>(PhantomData<(F, E)>);
+
+unsafe impl<F: DLIterFamily, E: Term<Type = F::LengthType>> DLIterFamily for DTakeFamily<F, E> {
+    type LengthType = F::LengthType;
+    type Iter<L: Term<Type = Self::LengthType>> = DTake<F, E, L>;
+    type Item = F::Item;
+
+    fn next<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let DTake(iter, e) = iter;
+        let iter = coerce(iter, F::equiv(s_a_plus_b_eq_s_add_a_b()));
+        let (iter, x) = F::next(iter, Add(value, e.clone()));
+        (DTake(iter, e), x)
+    }
+
+    fn finish(iter: Self::Iter<Zero<Self::LengthType>>) {
+        let DTake(iter, e) = iter;
+        let iter = coerce(iter, F::equiv(add_0_a_eq_a()));
+        iter.drop(e)
+    }
+}
+
+impl<F: DLIterFamily, E: Term<Type = F::LengthType>, L: Term<Type = F::LengthType>> DLIter for DTake<F, E, L> {
+    type LengthType = F::LengthType;
+    type Length = L;
+    type Family = DTakeFamily<F, E>;
+
+    fn drop(self, len: Value<L>) {
+        self.0.drop(Add(len, self.1))
+    }
+}
+
+/// Iterator adapter that yields every K-th item of an iterator of family F, starting with the first
+///
+/// The invariant L = ceil(N / K) holds for all values of this type, where N is the length of the inner iterator
+#[repr(C)]
+pub struct DStepBy<F: DLIterFamily, L: Term<Type = F::LengthType>>(
+    DPair<F::LengthType, DLIterFamily_Iter<F>>,
+    F::LengthType,
+    PhantomData<Value<L>>
+);
+
+pub struct DStepByFamily<F>(PhantomData<F>);
+
+unsafe impl<F: DLIterFamily> DLIterFamily for DStepByFamily<F> {
+    type LengthType = F::LengthType;
+    type Iter<L: Term<Type = Self::LengthType>> = DStepBy<F, L>;
+    type Item = F::Item;
+
+    fn next<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        _value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let DStepBy(iter, k, _) = iter;
+        make_guard!(g);
+        let (len, iter) = iter.into_inner(g);
+        let (iter, x) = match iter.next(len.clone()) {
+            Ok(res) => res,
+            // SAFETY: ceil(N / K) = L + 1 > 0, so N > 0
+            Err(_) => unsafe {core::hint::unreachable_unchecked()}
+        };
+        let len = Pred(len);
+        let skip = core::cmp::min(k.clone() - num_traits::One::one(), len.clone().into_inner());
+        make_guard!(g);
+        let skip = Var(g, skip);
+        // SAFETY: skip is at most len
+        let iter = iter.skip(len.clone(), skip.clone(), unsafe {ValueLe::axiom()});
+        // SAFETY: N decreased by min(K, N), so ceil(N / K) decreased by 1
+        (DStepBy(DPair::new(Sub(len, skip), iter), k, PhantomData), x)
+    }
+
+    fn finish(iter: Self::Iter<Zero<Self::LengthType>>) {
+        DLIter::drop(iter, Zero())
+    }
+}
+
+impl<F: DLIterFamily, L: Term<Type = F::LengthType>> DLIter for DStepBy<F, L> {
+    type LengthType = F::LengthType;
+    type Length = L;
+    type Family = DStepByFamily<F>;
+
+    fn drop(self, _len: Value<L>) {
+        make_guard!(g);
+        let (len, iter) = self.0.into_inner(g);
+        iter.drop(len);
+    }
+}
+
+/// Iterator adapter that yields the items of I together with their index in an iterator of length N
+///
+/// The invariant start + L = N holds for all values of this type, where start is the index of the next item and L is the length
+#[repr(C)]
+pub struct DEnumerate<I: DLIter, N: Term<Type = I::LengthType>>(I, I::LengthType, PhantomData<Value<N>>);
+
+pub struct DEnumerateFamily<F, N>(PhantomData<(F, N)>);
+
+unsafe impl<F: DLIterFamily, N: Term<Type = F::LengthType>> DLIterFamily for DEnumerateFamily<F, N> {
+    type LengthType = F::LengthType;
+    type Iter<L: Term<Type = Self::LengthType>> = DEnumerate<F::Iter<L>, N>;
+    type Item = (Fin<N>, F::Item);
+
+    fn next<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let DEnumerate(iter, start, _) = iter;
+        let (iter, x) = F::next(iter, value);
+        let next = start.clone() + num_traits::One::one();
+        // SAFETY: start + L + 1 = N
+        (DEnumerate(iter, next, PhantomData), (unsafe {Fin::new_unchecked(start)}, x))
+    }
+
+    fn finish(iter: Self::Iter<Zero<Self::LengthType>>) {
+        F::finish(iter.0)
+    }
+}
+
+impl<F: DLIterFamilyBack, N: Term<Type = F::LengthType>> DLIterFamilyBack for DEnumerateFamily<F, N> {
+    fn next_back<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let DEnumerate(iter, start, _) = iter;
+        let idx = start.clone() + value.clone().into_inner();
+        let (iter, x) = F::next_back(iter, value);
+        // SAFETY: start + L + 1 = N
+        (DEnumerate(iter, start, PhantomData), (unsafe {Fin::new_unchecked(idx)}, x))
+    }
+}
+
+impl<I: DLIter, N: Term<Type = I::LengthType>> DLIter for DEnumerate<I, N> {
+    type LengthType = I::LengthType;
+    type Length = I::Length;
+    type Family = DEnumerateFamily<I::Family, N>;
+
+    fn drop(self, len: Value<I::Length>) {
+        self.0.drop(len)
+    }
+}
+
+/// Result of DLIter::next and next_back: the rest of the iterator and the item, or the proof that the length is zero
+pub type DLIterNext<I> = Result<
+    (<<I as DLIter>::Family as DLIterFamily>::Iter<Pred<<I as DLIter>::Length>>, <<I as DLIter>::Family as DLIterFamily>::Item),
+    ValueEq<<I as DLIter>::Length, Zero<<I as DLIter>::LengthType>>
+>;
+
+/// Result of DIter::next and next_back
+pub type DIterNext<I> = Result<
+    (DIter<<<I as DLIter>::Family as DLIterFamily>::Iter<Pred<<I as DLIter>::Length>>>, <<I as DLIter>::Family as DLIterFamily>::Item),
+    ValueEq<<I as DLIter>::Length, Zero<<I as DLIter>::LengthType>>
+>;
+
+/// Result of DLIter::chain: the items of A followed by the items of B
+pub type DChainIter<A, B> = DChain<<A as DLIter>::Family, <B as DLIter>::Family, Add<<A as DLIter>::Length, <B as DLIter>::Length>>;
+
+/// Result of DLIter::take: the first N items of I
+pub type DTakeIter<I, N> = DTake<<I as DLIter>::Family, Sub<<I as DLIter>::Length, N>, N>;
+
+/// Result of DLIter::skip: I without its first N items
+pub type DSkipIter<I, N> = <<I as DLIter>::Family as DLIterFamily>::Iter<Sub<<I as DLIter>::Length, N>>;
+
+/// Result of DLIter::step_by: every K-th item of I
+pub type DStepByIter<I, K> = DStepBy<<I as DLIter>::Family, DivCeil<<I as DLIter>::Length, K>>;
+
+/// Iterator adapter that yields the items of I in reverse order
+#[repr(transparent)]
+pub struct DRev<I>(I);
+
+pub struct DRevFamily<F>(PhantomData<F>);
+
+unsafe impl<F: DLIterFamilyBack> DLIterFamily for DRevFamily<F> {
+    type LengthType = F::LengthType;
+    type Iter<L: Term<Type = Self::LengthType>> = DRev<F::Iter<L>>;
+    type Item = F::Item;
+
+    fn next<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let (iter, x) = F::next_back(iter.0, value);
+        (DRev(iter), x)
+    }
+
+    fn finish(iter: Self::Iter<Zero<Self::LengthType>>) {
+        F::finish(iter.0)
+    }
+}
+
+impl<F: DLIterFamilyBack> DLIterFamilyBack for DRevFamily<F> {
+    fn next_back<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let (iter, x) = F::next(iter.0, value);
+        (DRev(iter), x)
+    }
+}
+
+impl<I: DLIter> DLIter for DRev<I>
+    where I::Family: DLIterFamilyBack {
+    type LengthType = I::LengthType;
+    type Length = I::Length;
+    type Family = DRevFamily<I::Family>;
+
+    fn drop(self, len: Value<I::Length>) {
+        self.0.drop(len)
+    }
 }
 
 #[repr(C)]
@@ -90,6 +594,10 @@ impl<I: DLIter> Drop for DIter<I> {
 }
 
 impl<I: DLIter> DIter<I> {
+    pub fn new(iter: I, len: Value<I::Length>) -> Self {
+        DIter(ManuallyDrop::new(iter), len)
+    }
+
     pub fn into_inner(mut self) -> (I, Value<I::Length>) {
         let len = self.1.clone();
         let iter = unsafe {ManuallyDrop::take(&mut self.0)};
@@ -97,10 +605,8 @@ impl<I: DLIter> DIter<I> {
         (iter, len)
     }
     
-    pub fn next(self) -> Result<
-        (DIter<<I::Family as DLIterFamily>::Iter<Pred<I::Length>>>, <I::Family as DLIterFamily>::Item),
-        ValueEq<I::Length, Zero::<<I::Length as Term>::Type>>
-    > where Self: Sized
+    pub fn next(self) -> DIterNext<I>
+        where Self: Sized
     {
         let (iter, len) = self.into_inner();
         match iter.next(len.clone()) {
@@ -108,19 +614,123 @@ impl<I: DLIter> DIter<I> {
             Err(eq) => Err(eq)
         }
     }
+
+    pub fn next_back(self) -> DIterNext<I>
+        where Self: Sized, I::Family: DLIterFamilyBack
+    {
+        let (iter, len) = self.into_inner();
+        match iter.next_back(len.clone()) {
+            Ok((iter, x)) => Ok((DIter(ManuallyDrop::new(iter), Pred(len)), x)),
+            Err(eq) => Err(eq)
+        }
+    }
+
+    /// Iterate in reverse order
+    pub fn rev(self) -> DIter<DRev<I>>
+        where I::Family: DLIterFamilyBack {
+        let (iter, len) = self.into_inner();
+        DIter(ManuallyDrop::new(DRev(iter)), len)
+    }
+
+    /// Yield pairs of the index and the item, where the index is below the current length
+    pub fn enumerate(self) -> DIter<DEnumerate<I, I::Length>> {
+        let (iter, len) = self.into_inner();
+        DIter(ManuallyDrop::new(iter.enumerate()), len)
+    }
+
+    pub fn map<U, G: FnMut(<I::Family as DLIterFamily>::Item) -> U>(self, f: G) -> DIter<DMap<I, G, U>>
+        where DMap<I, G, U>: DLIter<Length = I::Length> {
+        let (iter, len) = self.into_inner();
+        DIter(ManuallyDrop::new(iter.map(f)), len)
+    }
+
+    pub fn inspect<G: FnMut(&<I::Family as DLIterFamily>::Item)>(self, f: G) -> DIter<DInspect<I, G>>
+        where DInspect<I, G>: DLIter<Length = I::Length> {
+        let (iter, len) = self.into_inner();
+        DIter(ManuallyDrop::new(iter.inspect(f)), len)
+    }
+
+    pub fn zip<B: DLIter<LengthType = I::LengthType, Length = I::Length>>(self, other: DIter<B>) -> DIter<DZip<I, B>> {
+        let (a, len) = self.into_inner();
+        let (b, _) = other.into_inner();
+        DIter(ManuallyDrop::new(a.zip(b)), len)
+    }
+
+    pub fn chain<B: DLIter<LengthType = I::LengthType>>(self, other: DIter<B>) -> DIter<DChainIter<I, B>>
+        where B::Family: DLIterFamily<Item = <I::Family as DLIterFamily>::Item> {
+        let (a, a_len) = self.into_inner();
+        let (b, b_len) = other.into_inner();
+        let len = Add(a_len.clone(), b_len.clone());
+        DIter(ManuallyDrop::new(a.chain(a_len, b, b_len)), len)
+    }
+
+    /// Yield only the first n items, dropping the rest
+    pub fn take<N: Term<Type = I::LengthType>>(self, n: Value<N>, le: ValueLe<N, I::Length>) -> DIter<DTakeIter<I, N>> {
+        let (iter, len) = self.into_inner();
+        DIter(ManuallyDrop::new(iter.take(len, n.clone(), le)), n)
+    }
+
+    /// Drop the first n items and return the iterator over the rest
+    pub fn skip<N: Term<Type = I::LengthType>>(self, n: Value<N>, le: ValueLe<N, I::Length>) -> DIter<DSkipIter<I, N>> {
+        let (iter, len) = self.into_inner();
+        let rest = Sub(len.clone(), n.clone());
+        DIter(ManuallyDrop::new(iter.skip(len, n, le)), rest)
+    }
+
+    /// Yield every k-th item, starting with the first
+    pub fn step_by<K: Term<Type = I::LengthType>>(self, k: Value<K>, gt: ValueGt<K, Zero<I::LengthType>>) -> DIter<DStepByIter<I, K>> {
+        let (iter, len) = self.into_inner();
+        let steps = DivCeil(len.clone(), k.clone());
+        DIter(ManuallyDrop::new(iter.step_by(len, k, gt)), steps)
+    }
+
+    /// Collect all items into a container whose length is the length of the iterator
+    pub fn collect_d<C: FromDLIter<I>>(self) -> C {
+        let (iter, len) = self.into_inner();
+        C::from_dliter(iter, len)
+    }
+
+    /// Convert into a std iterator with an exact size hint
+    pub fn into_std(self) -> DIterPair<I::LengthType, I::Family> {
+        let (iter, len) = self.into_inner();
+        DIterPair::new(iter, len)
+    }
 }
 
-#[repr(transparent)]
-struct DIterPair<T, F>(Option<DPair<T, DLIterFamily_Iter<F>>>)
+impl<I: DLIter> IntoIterator for DIter<I>
+    where usize: TryFrom<I::LengthType> {
+    type Item = <I::Family as DLIterFamily>::Item;
+    type IntoIter = DIterPair<I::LengthType, I::Family>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_std()
+    }
+}
+
+/// Iterator over the items of an iterator of family F whose length is stored at runtime
+///
+/// Implements the std iterator traits with an exact size hint, so dependent iterators can be used in for loops and with std adapters
+pub struct DIterPair<T, F>(Option<DPair<T, DLIterFamily_Iter<F>>>)
         where F: DLIterFamily<LengthType = T>;
 
-impl_newtype! {
-    impl [T, F] [T2, F2] DIterPair(Option<DPair<T, DLIterFamily_Iter<F>>>)
-        where [F: DLIterFamily<LengthType = T>] [F2: DLIterFamily<LengthType = T2>]
+impl<T, F: DLIterFamily<LengthType = T>> DIterPair<T, F> {
+    pub fn new<L: Term<Type = T>>(iter: F::Iter<L>, len: Value<L>) -> Self {
+        DIterPair(Some(DPair::new(len, iter)))
+    }
+}
+
+impl<T, F: DLIterFamily<LengthType = T>> Drop for DIterPair<T, F> {
+    fn drop(&mut self) {
+        if let Some(pair) = self.0.take() {
+            make_guard!(g);
+            let (len, iter) = pair.into_inner(g);
+            iter.drop(len);
+        }
+    }
 }
 
 impl<T, F: DLIterFamily<LengthType = T>> Iterator for DIterPair<T, F>
-    where T: UInt {
+    where T: UInt, usize: TryFrom<T> {
     type Item = F::Item;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -139,8 +749,54 @@ impl<T, F: DLIterFamily<LengthType = T>> Iterator for DIterPair<T, F>
             None
         }
     }
+
+    /// Exact unless the length does not fit in usize
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            Some(pair) => {
+                make_guard!(g);
+                let (len, _) = pair.get(g);
+                match usize::try_from(len.clone().into_inner()) {
+                    Ok(len) => (len, Some(len)),
+                    Err(_) => (usize::MAX, None)
+                }
+            },
+            None => (0, Some(0))
+        }
+    }
+}
+
+impl<T, F: DLIterFamilyBack<LengthType = T>> DoubleEndedIterator for DIterPair<T, F>
+    where T: UInt, usize: TryFrom<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if let Some(pair) = self.0.take() {
+            make_guard!(g);
+            let (len, iter) = pair.into_inner(g);
+            let res = iter.next_back(len.clone());
+            match res {
+                Ok((iter, x)) => {
+                    self.0 = Some(DPair::new(Pred(len), iter));
+                    Some(x)
+                },
+                Err(_eq) => None
+            }
+        } else {
+            None
+        }
+    }
 }
 
+impl<T, F: DLIterFamily<LengthType = T>> ExactSizeIterator for DIterPair<T, F>
+    where T: UInt, usize: From<T> {}
+
+impl<T, F: DLIterFamily<LengthType = T>> core::iter::FusedIterator for DIterPair<T, F>
+    where T: UInt, usize: TryFrom<T> {}
+
+// SAFETY: size_hint returns the exact length, since it always fits in usize
+#[cfg(feature = "trusted_len")]
+unsafe impl<T, F: DLIterFamily<LengthType = T>> core::iter::TrustedLen for DIterPair<T, F>
+    where T: UInt, usize: From<T> {}
+
 #[repr(C)]
 pub struct DIterBridge<I: Iterator, L: Term, U: Unreachable>(I, U, PhantomData<Value<L>>);
 
@@ -167,6 +823,20 @@ unsafe impl<I: Iterator, LT: UInt, U: Unreachable> DLIterFamily for DIterBridgeF
     }
 }
 
+impl<I: DoubleEndedIterator, LT: UInt, U: Unreachable> DLIterFamilyBack for DIterBridgeFamily<I, LT, U> {
+    fn next_back<L: Term<Type = Self::LengthType>>(
+            mut iter: Self::Iter<Succ<L>>,
+            _value: Value<L>
+        ) -> (Self::Iter<L>, Self::Item) {
+        match DoubleEndedIterator::next_back(&mut iter.0) {
+            Some(v) => (DIterBridge(iter.0, iter.1, PhantomData), v),
+            None => {
+                iter.1.unreachable();
+            }
+        }
+    }
+}
+
 impl<I: Iterator, L: Term, U: Unreachable> DLIter for DIterBridge<I, L, U>
     where L::Type: UInt {
     type LengthType = L::Type;
@@ -198,9 +868,161 @@ impl<'a, I: ExactSizeIterator, U: Unreachable> DIterBridge<I, Var<'a, usize>, U>
 }
 
 #[cfg(feature = "trusted_len")]
-impl<'a, I: ExactSizeIterator + core::iter::TrustedLen> DIterBridge<I, Var<'a, usize>, UnreachableUnchecked> {
+impl<'a, I: ExactSizeIterator + core::iter::TrustedLen> DIterBridge<I, Var<'a, usize>, crate::unreachable::UnreachableUnchecked> {
     pub fn new_trusted(guard: Guard<'a>, iter: I) -> (Self, Value<Var<'a, usize>>) {
-        use core::unreachable::UnreachableUnchecked;
-        unsafe {Self::new_exact(guard, iter, unsafe {UnreachableUnchecked::new()})}
+        Self::new_exact(guard, iter, unsafe {crate::unreachable::UnreachableUnchecked::new()})
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_iter_adapters() {
+    use alloc::vec;
+    use crate::int::ConstUsize;
+    use crate::slice::DSlice;
+    use crate::vec::DVec;
+
+    let a = [1, 2, 3];
+    let b = [10, 20, 30];
+    let n = ConstUsize::<3>();
+    let sa: &DSlice<i32, _> = (&a).into();
+    let sb: &DSlice<i32, _> = (&b).into();
+    let mut seen = 0;
+    let zipped = sa.iter().zip(sb.iter()).map(|(x, y)| x + y).inspect(|_| seen += 1);
+    let v = DVec::new().add_iter(zipped, n);
+    assert_eq!(seen, 3);
+    let len = v.len();
+    let chained = v.into_iter().chain(len, sa.iter().map(|x| *x), n);
+    let v = DVec::new().add_iter(chained, Add(len, n));
+    assert_eq!(v.into_vec(), vec![11, 22, 33, 1, 2, 3]);
+}
+
+#[test]
+pub fn test_chain_empty() {
+    use crate::int::ConstUsize;
+    use crate::slice::DSlice;
+
+    let a = [1, 2];
+    let e: [i32; 0] = [];
+    let sa: &DSlice<i32, _> = (&a).into();
+    let se: &DSlice<i32, _> = (&e).into();
+    let it = DIter::new(se.iter(), ConstUsize::<0>()).chain(DIter::new(sa.iter(), ConstUsize::<2>()));
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(*x, 1);
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(*x, 2);
+    assert!(it.next().is_err());
+
+    let it = DIter::new(sa.iter(), ConstUsize::<2>()).chain(DIter::new(se.iter(), ConstUsize::<0>()));
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(*x, 1);
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(*x, 2);
+    assert!(it.next().is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_take_skip_step_by() {
+    use alloc::rc::Rc;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use crate::int::{ConstU8, ConstUsize};
+    use crate::slice::DSlice;
+    use crate::vec::DVec;
+    use crate::term::{value_le, value_le_or_gt};
+    use crate::unreachable::UnreachablePanic;
+
+    let x = Rc::new(0);
+    let five = ConstUsize::<5>();
+    let two = ConstUsize::<2>();
+
+    let make = || DVec::new().add_iter(DIterBridge::new_assert((0..5).map(|i| (i, x.clone())), UnreachablePanic), five);
+
+    let v = make();
+    let len = v.len();
+    let le = value_le(two, len).unwrap();
+    let it = v.into_iter().take(len, two, le).map(|(i, _)| i);
+    let v = DVec::new().add_iter(it, two);
+    assert_eq!(v.into_vec(), vec![0, 1]);
+    assert_eq!(Rc::strong_count(&x), 1);
+
+    let v = make();
+    assert_eq!(Rc::strong_count(&x), 6);
+    let len = v.len();
+    let le = value_le(two, len).unwrap();
+    let it = v.into_iter().skip(len, two, le).map(|(i, _)| i);
+    assert_eq!(Rc::strong_count(&x), 4);
+    let v = DVec::new().add_iter(it, Sub(len, two));
+    assert_eq!(v.into_vec(), vec![2, 3, 4]);
+    assert_eq!(Rc::strong_count(&x), 1);
+
+    let a = [1, 2, 3, 4, 5];
+    let sa: &DSlice<i32, _> = (&a).into();
+    let gt = value_le_or_gt(two, Zero()).err().unwrap();
+    let it = DIter::new(sa.iter(), sa.len()).step_by(two, gt).map(|x| *x);
+    let (it, len) = it.into_inner();
+    assert_eq!(len.into_inner(), 3);
+    let v = DVec::new().add_iter(it, len);
+    assert_eq!(v.into_vec(), vec![1, 3, 5]);
+
+    // the length must not overflow at the edge of the length type
+    let n = ConstU8::<250>();
+    let k = ConstU8::<10>();
+    let gt = value_le_or_gt(k, Zero()).err().unwrap();
+    let (it, len) = DIter::new(DIterBridge::new_assert(0..250, UnreachablePanic), n).step_by(k, gt).into_inner();
+    assert_eq!(len.into_inner(), 25);
+    assert_eq!(DVec::new().add_iter(it, len).into_vec(), (0..250).step_by(10).collect::<Vec<_>>());
+    let n = ConstU8::<255>();
+    let k = ConstU8::<200>();
+    let gt = value_le_or_gt(k, Zero()).err().unwrap();
+    let (it, len) = DIter::new(DIterBridge::new_assert(0..255, UnreachablePanic), n).step_by(k, gt).into_inner();
+    assert_eq!(DVec::new().add_iter(it, len).into_vec(), vec![0, 200]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_into_std() {
+    use alloc::rc::Rc;
+    use alloc::vec;
+    use alloc::vec::Vec;
+    use crate::int::{ConstU64, ConstUsize};
+    use crate::slice::DSlice;
+    use crate::vec::DVec;
+    use crate::unreachable::UnreachablePanic;
+
+    let a = [1, 2, 3];
+    let n = ConstUsize::<3>();
+    let sa: &DSlice<i32, _> = (&a).into();
+    let mut sum = 0;
+    for x in DIter::new(sa.iter(), n) {
+        sum += *x;
+    }
+    assert_eq!(sum, 6);
+
+    let mut it = DIter::new(sa.iter(), n).into_std();
+    assert_eq!(it.size_hint(), (3, Some(3)));
+    assert_eq!(it.next_back(), Some(&3));
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.map(|x| x * 10).collect::<Vec<_>>(), vec![10, 20]);
+
+    let x = Rc::new(0);
+    let v: DVec<Rc<i32>, _> = sa.iter().map(|_| x.clone()).collect_d(n);
+    let len = v.len();
+    let mut it = DIter::new(v.into_iter(), len).into_std();
+    assert!(it.next().is_some());
+    assert_eq!(Rc::strong_count(&x), 3);
+    drop(it);
+    assert_eq!(Rc::strong_count(&x), 1);
+
+    // any unsigned length type can be used as a std iterator
+    let it = DIter::new(DIterBridge::new_assert(0..4, UnreachablePanic), ConstU64::<4>()).into_std();
+    assert_eq!(it.size_hint(), (4, Some(4)));
+    assert_eq!(it.sum::<i32>(), 6);
+
+    #[cfg(feature = "trusted_len")]
+    {
+        fn trusted<I: core::iter::TrustedLen>(_: &I) {}
+        trusted(&DIter::new(sa.iter(), n).into_std());
     }
 }
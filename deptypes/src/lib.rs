@@ -6,6 +6,9 @@
 #[cfg(feature = "std")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 pub use num_traits;
 
 #[macro_use]
@@ -34,16 +37,35 @@ pub mod int;
 pub mod bool;
 pub mod slice;
 pub mod fin;
+pub mod range;
 pub mod unreachable;
 pub mod iter;
 pub mod pair;
 pub mod result;
 pub mod loops;
 pub mod kinds;
+pub mod arrayvec;
+pub mod string;
+pub mod cursor;
 
 #[cfg(feature = "std")]
 pub mod vec;
 
+#[cfg(feature = "std")]
+pub mod vec_deque;
+
+#[cfg(feature = "std")]
+pub mod matrix;
+
+#[cfg(feature = "std")]
+pub mod boxed;
+
+#[cfg(feature = "std")]
+pub mod rc;
+
+#[cfg(feature = "std")]
+pub mod io;
+
 /// For usage by macro-generated code
 #[doc(hidden)]
 pub use generics2::parse as generics_parse;
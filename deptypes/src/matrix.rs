@@ -0,0 +1,134 @@
+use core::ops::{Index, IndexMut};
+
+use crate::fin::Fin;
+use crate::guard::make_guard;
+use crate::int::mul::{mul_add_lt, mul_commutative};
+use crate::ops::{Add, Mul};
+use crate::slice::DSlice;
+use crate::term::{Term, Value};
+use crate::transmutable::coerce;
+use crate::vec::DVec;
+
+use alloc::vec::Vec;
+
+/// Matrix with R rows and C columns, stored in row-major order in a single buffer of length R * C
+#[repr(C)]
+pub struct DMatrix<T, R: Term<Type = usize>, C: Term<Type = usize>>(DVec<T, Mul<R, C>>, Value<R>, Value<C>);
+
+impl<T, R: Term<Type = usize>, C: Term<Type = usize>> DMatrix<T, R, C> {
+    pub fn from_dvec(v: DVec<T, Mul<R, C>>, rows: Value<R>, cols: Value<C>) -> Self {
+        DMatrix(v, rows, cols)
+    }
+
+    pub fn from_rows(v: DVec<DVec<T, C>, R>, cols: Value<C>) -> Self {
+        let rows = v.len();
+        let len = Mul(rows, cols);
+        let mut buf = Vec::with_capacity(len.into_inner());
+        for row in v.into_vec() {
+            buf.append(&mut row.into_vec());
+        }
+        DMatrix(unsafe {DVec::new_unchecked(buf, len)}, rows, cols)
+    }
+
+    pub fn into_dvec(self) -> DVec<T, Mul<R, C>> {
+        self.0
+    }
+
+    pub fn rows(&self) -> Value<R> {
+        self.1
+    }
+
+    pub fn cols(&self) -> Value<C> {
+        self.2
+    }
+
+    fn offset(&self, row: Fin<R>, col: Fin<C>) -> Fin<Mul<R, C>> {
+        make_guard!(g);
+        make_guard!(h);
+        let (i, i_lt) = row.into_lt(g);
+        let (j, j_lt) = col.into_lt(h);
+        Fin::from_lt(Add(Mul(i, self.2), j), mul_add_lt(i_lt, j_lt))
+    }
+
+    pub fn row(&self, row: Fin<R>) -> &DSlice<T, C> {
+        self.0.as_rows(self.2, row)
+    }
+
+    pub fn row_mut(&mut self, row: Fin<R>) -> &mut DSlice<T, C> {
+        let cols = self.2;
+        self.0.as_rows_mut(cols, row)
+    }
+
+    pub fn transpose(self) -> DMatrix<T, C, R> {
+        let DMatrix(v, rows, cols) = self;
+        let (r, c) = (rows.into_inner(), cols.into_inner());
+        let mut v = v.into_vec();
+        let mut buf = Vec::with_capacity(r * c);
+        unsafe {
+            let ptr = v.as_ptr();
+            for j in 0..c {
+                for i in 0..r {
+                    buf.push(ptr.add(i * c + j).read());
+                }
+            }
+            // the elements have all been moved to buf
+            v.set_len(0);
+        }
+        let len = coerce(Mul(rows, cols), mul_commutative());
+        DMatrix(unsafe {DVec::new_unchecked(buf, len)}, cols, rows)
+    }
+
+    /// Matrix product, which only typechecks if the columns of self are the rows of other
+    pub fn matmul<K: Term<Type = usize>>(&self, other: &DMatrix<T, C, K>) -> DMatrix<T, R, K>
+        where T: Clone + num_traits::Zero + core::ops::Mul<Output = T> {
+        let len = Mul(self.rows(), other.cols());
+        let mut buf = Vec::with_capacity(len.into_inner());
+        for i in Fin::range(self.rows()) {
+            for k in Fin::range(other.cols()) {
+                let mut acc = T::zero();
+                for j in Fin::range(self.cols()) {
+                    acc = acc + self[(i, j)].clone() * other[(j, k)].clone();
+                }
+                buf.push(acc);
+            }
+        }
+        DMatrix(unsafe {DVec::new_unchecked(buf, len)}, self.rows(), other.cols())
+    }
+}
+
+impl<T, R: Term<Type = usize>, C: Term<Type = usize>> Index<(Fin<R>, Fin<C>)> for DMatrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, (row, col): (Fin<R>, Fin<C>)) -> &Self::Output {
+        &self.0[self.offset(row, col)]
+    }
+}
+
+impl<T, R: Term<Type = usize>, C: Term<Type = usize>> IndexMut<(Fin<R>, Fin<C>)> for DMatrix<T, R, C> {
+    fn index_mut(&mut self, (row, col): (Fin<R>, Fin<C>)) -> &mut Self::Output {
+        let offset = self.offset(row, col);
+        &mut self.0[offset]
+    }
+}
+
+#[test]
+pub fn test_matrix() {
+    use alloc::vec;
+    use crate::int::ConstUsize;
+
+    let make = || {
+        let row = |a: [i32; 3]| unsafe {DVec::new_unchecked(a.to_vec(), ConstUsize::<3>())};
+        let rows = unsafe {DVec::new_unchecked(vec![row([1, 2, 3]), row([4, 5, 6])], ConstUsize::<2>())};
+        DMatrix::from_rows(rows, ConstUsize())
+    };
+    let m = make();
+    let i = Fin::from(m.rows(), 1).unwrap();
+    let j = Fin::from(m.cols(), 2).unwrap();
+    assert_eq!(m[(i, j)], 6);
+    assert_eq!(m.row(i)[j], 6);
+
+    let t = make().transpose();
+    assert_eq!(t[(j, i)], 6);
+    let p = m.matmul(&t);
+    assert_eq!(p.into_dvec().into_vec(), vec![14, 32, 32, 77]);
+}
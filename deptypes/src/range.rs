@@ -0,0 +1,140 @@
+use core::marker::PhantomData;
+
+use crate::fin::Fin;
+use crate::guard::Guard;
+use crate::int::{Succ, Zero};
+use crate::int::uint::UInt;
+use crate::iter::{DIter, DLIter, DLIterFamily};
+use crate::ops::Sub;
+use crate::term::{value_le, Term, Value, ValueLe};
+use crate::var::Var;
+
+/// Range [A, B) together with evidence that A <= B
+pub struct DRange<A: Term, B: Term<Type = A::Type>> {
+    start: Value<A>,
+    end: Value<B>,
+    le: ValueLe<A, B>,
+}
+
+impl<A: Term, B: Term<Type = A::Type>> Clone for DRange<A, B>
+    where A::Type: Clone {
+    fn clone(&self) -> Self {
+        DRange {start: self.start.clone(), end: self.end.clone(), le: self.le}
+    }
+}
+
+impl<A: Term, B: Term<Type = A::Type>> Copy for DRange<A, B>
+    where A::Type: Copy {}
+
+impl<A: Term, B: Term<Type = A::Type>> DRange<A, B> {
+    pub fn new(start: Value<A>, end: Value<B>, le: ValueLe<A, B>) -> Self {
+        DRange {start, end, le}
+    }
+
+    pub fn into_inner(self) -> (Value<A>, Value<B>, ValueLe<A, B>) {
+        (self.start, self.end, self.le)
+    }
+
+    pub fn le(&self) -> ValueLe<A, B> {
+        self.le
+    }
+}
+
+impl<A: Term, B: Term<Type = A::Type>> DRange<A, B>
+    where A::Type: UInt {
+    pub fn start(&self) -> Value<A> {
+        self.start.clone()
+    }
+
+    pub fn end(&self) -> Value<B> {
+        self.end.clone()
+    }
+
+    pub fn len(&self) -> Value<Sub<B, A>> {
+        Sub(self.end(), self.start())
+    }
+
+    /// Iterate over the indices in the range, as indices into anything of length at least B
+    pub fn iter<L: Term<Type = A::Type>>(&self, _le: ValueLe<B, L>) -> DIter<DRangeIter<L, Sub<B, A>>> {
+        DIter::new(DRangeIter(self.start().into_inner(), PhantomData), self.len())
+    }
+}
+
+impl<'a, 'b, LT: UInt> DRange<Var<'a, LT>, Var<'b, LT>> {
+    /// Build the range [start, end) of indices into something of length L, if start <= end
+    pub fn from_fins<L: Term<Type = LT>>(ga: Guard<'a>, gb: Guard<'b>, start: Fin<Succ<L>>, end: Fin<Succ<L>>) -> Option<(Self, ValueLe<Var<'b, LT>, L>)> {
+        let start = Var(ga, Fin::into_inner(start));
+        let end = Var(gb, Fin::into_inner(end));
+        let le = value_le(start.clone(), end.clone())?;
+        // SAFETY: end < Succ<L>
+        Some((DRange {start, end, le}, unsafe {ValueLe::axiom()}))
+    }
+}
+
+/// Iterator over N consecutive indices into something of length L
+///
+/// The invariant start + N <= L holds for all values of this type
+#[repr(C)]
+pub struct DRangeIter<L: Term, N: Term<Type = L::Type>>(L::Type, PhantomData<(Value<L>, Value<N>)>);
+
+pub struct DRangeIterFamily<L>(PhantomData<fn() -> L>);
+
+unsafe impl<L: Term> DLIterFamily for DRangeIterFamily<L>
+    where L::Type: UInt {
+    type LengthType = L::Type;
+    type Iter<N: Term<Type = Self::LengthType>> = DRangeIter<L, N>;
+    type Item = Fin<L>;
+
+    fn next<N: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<N>>,
+        _value: Value<N>
+    ) -> (Self::Iter<N>, Self::Item) {
+        let x = iter.0;
+        let next = x.clone() + num_traits::One::one();
+        // SAFETY: x + N + 1 <= L
+        (DRangeIter(next, PhantomData), unsafe {Fin::new_unchecked(x)})
+    }
+
+    fn finish(_iter: Self::Iter<Zero<Self::LengthType>>) {
+    }
+}
+
+impl<L: Term, N: Term<Type = L::Type>> DLIter for DRangeIter<L, N>
+    where L::Type: UInt {
+    type LengthType = L::Type;
+    type Length = N;
+    type Family = DRangeIterFamily<L>;
+
+    fn drop(self, _len: Value<N>) {
+    }
+}
+
+#[test]
+pub fn test_drange() {
+    use crate::guard::make_guard;
+    use crate::slice::DSlice;
+
+    let a = [1, 2, 3, 4, 5];
+    make_guard!(g);
+    let (s, len) = DSlice::new_ref(g, &a);
+    let start = Fin::from(Succ(len), 1).unwrap();
+    let end = Fin::from(Succ(len), 4).unwrap();
+    make_guard!(ga);
+    make_guard!(gb);
+    let (r, le) = DRange::from_fins(ga, gb, start, end).unwrap();
+    let sub = s.range(r, le);
+    assert_eq!(sub.as_slice(), &[2, 3, 4]);
+
+    let it = r.iter(le);
+    let (it, i) = it.next().ok().unwrap();
+    assert_eq!(s[i], 2);
+    let (it, i) = it.next().ok().unwrap();
+    assert_eq!(s[i], 3);
+    let (it, i) = it.next().ok().unwrap();
+    assert_eq!(s[i], 4);
+    assert!(it.next().is_err());
+
+    make_guard!(ga);
+    make_guard!(gb);
+    assert!(DRange::from_fins(ga, gb, end, start).is_none());
+}
@@ -0,0 +1,107 @@
+use core::ops::Deref;
+
+use crate::boxed::DBox;
+use crate::int::uint::UInt;
+use crate::slice::DSlice;
+use crate::term::{Term, Value, ValueEq};
+use crate::transmutable::{Equiv, Transm};
+use crate::vec::DVec;
+
+use alloc::rc::Rc;
+use alloc::sync::Arc;
+
+macro_rules! impl_shared_slice {
+    ($(#[$m:meta])* $S:ident $P:ident $into:ident) => {
+        $(#[$m])*
+        #[repr(C)]
+        pub struct $S<T, L: Term>($P<[T]>, Value<L>);
+
+        impl<T, L: Term> $S<T, L> {
+            pub fn equiv<L1: Term>(_: ValueEq<L, L1>) -> Equiv<$S<T, L>, $S<T, L1>> {
+                unsafe {Equiv::axiom()}
+            }
+
+            pub fn transm<T1, L1: Term>(_: Transm<T, T1>, _: ValueEq<L, L1>) -> Transm<$S<T, L>, $S<T1, L1>> {
+                unsafe {Transm::axiom()}
+            }
+
+            /// # Safety
+            ///
+            /// x must have length len
+            pub unsafe fn new_unchecked(x: $P<[T]>, len: Value<L>) -> Self {
+                $S(x, len)
+            }
+
+            pub fn into_inner(self) -> ($P<[T]>, Value<L>) {
+                (self.0, self.1)
+            }
+
+            pub fn len(&self) -> Value<L>
+                where L::Type: Clone {
+                self.1.clone()
+            }
+        }
+
+        impl<T, L: Term> DBox<T, L> {
+            pub fn $into(self) -> $S<T, L> {
+                let (x, len) = self.into_inner();
+                $S($P::from(x), len)
+            }
+        }
+
+        impl<T, L: Term> From<DBox<T, L>> for $S<T, L> {
+            fn from(x: DBox<T, L>) -> Self {
+                x.$into()
+            }
+        }
+
+        impl<T, L: Term> From<DVec<T, L>> for $S<T, L>
+            where usize: From<L::Type>, L::Type: UInt {
+            fn from(x: DVec<T, L>) -> Self {
+                x.into_boxed().$into()
+            }
+        }
+
+        impl<T, L: Term> Clone for $S<T, L>
+            where L::Type: Clone {
+            fn clone(&self) -> Self {
+                $S(self.0.clone(), self.1.clone())
+            }
+        }
+
+        impl<T, L: Term> Deref for $S<T, L> {
+            type Target = DSlice<T, L>;
+
+            fn deref(&self) -> &Self::Target {
+                unsafe {DSlice::new_ref_unchecked(&self.0)}
+            }
+        }
+    }
+}
+
+impl_shared_slice! {
+    /// Reference-counted immutable slice whose length is L
+    DRc Rc into_rc
+}
+
+impl_shared_slice! {
+    /// Atomically reference-counted immutable slice whose length is L
+    DArc Arc into_arc
+}
+
+#[test]
+pub fn test_shared_slices() {
+    use alloc::vec;
+    use crate::fin::Fin;
+    use crate::guard::make_guard;
+
+    make_guard!(g);
+    let a = DVec::from(g, vec![1, 2, 3]);
+    let b = a.into_boxed();
+    let c = b.clone().into_rc();
+    let d = c.clone();
+    let e = DArc::from(b.clone().into_dvec());
+    let i = Fin::from(b.len(), 2).unwrap();
+    assert_eq!((b[i], d[i], e[i]), (3, 3, 3));
+    assert_eq!(b.into_dvec().into_vec(), vec![1, 2, 3]);
+}
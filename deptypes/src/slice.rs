@@ -1,17 +1,33 @@
-use core::ops::IndexMut;
+use core::ops::{Deref, DerefMut, IndexMut};
 use core::ptr::NonNull;
 use core::{marker::PhantomData, ops::Index};
 use crate::guard::Guard;
 
 use crate::int::{Succ, Zero};
-use crate::iter::{DLIter, DLIterFamily};
+use crate::ops::{Add, Mul, Sub};
+use crate::int::sub::b_plus_sub_a_b_eq_a;
+use crate::iter::{DEnumerate, DIter, DLIter, DLIterFamily, DLIterFamilyBack};
 //use crate::iter::DIterNoLen;
-use crate::term::{Term, Value};
+use crate::term::{Term, Value, ValueEq, ValueGt, ValueLe};
 use crate::var::Var;
 use crate::int::{ConstUsize, uint::UInt};
 use crate::fin::Fin;
+use crate::range::DRange;
+#[cfg(feature = "std")]
+use crate::vec::DVec;
 
-pub struct DSlice<T, L: Term>(PhantomData<(Value<L>, [T])>, [T; 0]);
+/// Slice whose length is the value of L
+///
+/// This is a dynamically sized wrapper of [T], so references to it carry the runtime length as well and
+/// keep provenance over the whole slice, which is needed for derived pointers to be valid
+#[repr(transparent)]
+pub struct DSlice<T, L: Term>(PhantomData<Value<L>>, [T]);
+
+/// Result of DSlice::split_at: the first M elements and the rest
+pub type DSliceSplit<'x, T, L, M> = (&'x DSlice<T, M>, &'x DSlice<T, Sub<L, M>>);
+
+/// Result of DSlice::split_at_mut: the first M elements and the rest
+pub type DSliceSplitMut<'x, T, L, M> = (&'x mut DSlice<T, M>, &'x mut DSlice<T, Sub<L, M>>);
 
 impl<'a, T, L: Term> Index<Fin<L>> for DSlice<T, L>
     where usize: From<L::Type>, L::Type: UInt {
@@ -19,7 +35,8 @@ impl<'a, T, L: Term> Index<Fin<L>> for DSlice<T, L>
 
     fn index(&self, index: Fin<L>) -> &Self::Output
     where usize: From<L::Type>, L::Type: UInt {
-        unsafe {core::mem::transmute((self as *const _ as *const T).add(usize::from(Fin::into_inner(index))))}
+        // SAFETY: index < L, which is the length of the slice
+        unsafe {self.1.get_unchecked(usize::from(Fin::into_inner(index)))}
     }
 }
 
@@ -27,7 +44,8 @@ impl<'a, T, L: Term> IndexMut<Fin<L>> for DSlice<T, L>
     where usize: From<L::Type>, L::Type: UInt {
     fn index_mut(&mut self, index: Fin<L>) -> &mut Self::Output
         where usize: From<L::Type>, L::Type: UInt {
-        unsafe {core::mem::transmute((self as *mut _ as *mut T).add(usize::from(Fin::into_inner(index))))}
+        // SAFETY: index < L, which is the length of the slice
+        unsafe {self.1.get_unchecked_mut(usize::from(Fin::into_inner(index)))}
     }
 }
 
@@ -62,6 +80,20 @@ macro_rules! impl_slice_iter {
             }    
         }
 
+        impl<'a, T, LT: UInt> DLIterFamilyBack for $F<'a, T, LT>
+            where usize: From<LT> {
+            fn next_back<L: Term<Type = Self::LengthType>>(
+                iter: Self::Iter<Succ<L>>,
+                value: Value<L>
+            ) -> (Self::Iter<L>, Self::Item) {
+                // SAFETY: L + 1 elements remain, so the last one is at offset L
+                let mut ptr = unsafe {NonNull::new_unchecked(iter.ptr.as_ptr().add(usize::from(value.into_inner())))};
+                let ptr = &mut ptr;
+                let r = unsafe {ptr.$as_ref()};
+                ($S {ptr: iter.ptr, _marker: PhantomData}, r)
+            }
+        }
+
         impl<'a, T, L: Term> DLIter for $S<'a, T, L>
             where L::Type: UInt {
             type LengthType = L::Type;
@@ -78,20 +110,517 @@ impl_slice_iter! {DSliceIter DSliceIterFamily as_ref}
 impl_slice_iter! {DSliceIterMut DSliceIterMutFamily as_mut mut}
 
 impl<T, L: Term> DSlice<T, L> {
+    /// SAFETY: the length of x must be the value of L
     pub unsafe fn new_ref_unchecked(x: &[T]) -> &Self {
-        core::mem::transmute(x.as_ptr())
+        &*(x as *const [T] as *const Self)
     }
 
+    /// SAFETY: the length of x must be the value of L
     pub unsafe fn new_mut_unchecked(x: &mut [T]) -> &mut Self {
-        core::mem::transmute(x.as_mut_ptr())
+        &mut *(x as *mut [T] as *mut Self)
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        &self.1
+    }
+
+    pub fn len(&self) -> Value<L>
+        where L::Type: TryFrom<usize> {
+        // SAFETY: the length of the slice is the value of L, so it fits in L::Type
+        unsafe {Value::new_unchecked(L::Type::try_from(self.1.len()).unwrap_unchecked())}
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.1
     }
 
     pub fn iter(&self) -> DSliceIter<'_, T, L> {
-        DSliceIter {ptr: unsafe {NonNull::new_unchecked(&self.1[0] as *const T as *mut T)}, _marker: PhantomData}
+        DSliceIter {ptr: NonNull::from(&self.1).cast(), _marker: PhantomData}
     }
 
     pub fn iter_mut(&mut self) -> DSliceIterMut<'_, T, L> {
-        DSliceIterMut {ptr: unsafe {NonNull::new_unchecked(&self.1[0] as *const T as *mut T)}, _marker: PhantomData}
+        DSliceIterMut {ptr: NonNull::from(&mut self.1).cast(), _marker: PhantomData}
+    }
+
+    /// Iterate over pairs of the index and a reference to the element
+    pub fn iter_enumerated(&self) -> DEnumerate<DSliceIter<'_, T, L>, L>
+        where L::Type: UInt {
+        self.iter().enumerate()
+    }
+
+    pub fn reverse(&mut self) {
+        self.1.reverse()
+    }
+
+    pub fn fill(&mut self, x: T)
+        where T: Clone {
+        self.1.fill(x)
+    }
+
+    /// Copy all elements from src, which has the same length
+    pub fn copy_from_dslice(&mut self, src: &DSlice<T, L>)
+        where T: Copy {
+        // SAFETY: both slices have length L, and they can't overlap since self is borrowed mutably
+        unsafe {core::ptr::copy_nonoverlapping(src.1.as_ptr(), self.1.as_mut_ptr(), self.1.len())}
+    }
+
+    /// Clone all elements from src, which has the same length
+    pub fn clone_from_dslice(&mut self, src: &DSlice<T, L>)
+        where T: Clone {
+        for (x, y) in self.1.iter_mut().zip(src.1.iter()) {
+            x.clone_from(y);
+        }
+    }
+}
+
+impl<T, L: Term> DSlice<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    pub fn swap(&mut self, a: Fin<L>, b: Fin<L>) {
+        let ptr = self.1.as_mut_ptr();
+        // SAFETY: a, b < L
+        unsafe {core::ptr::swap(ptr.add(usize::from(a.into_inner())), ptr.add(usize::from(b.into_inner())))}
+    }
+
+    /// Rotate the slice so that the element at mid becomes the first one
+    pub fn rotate_left(&mut self, mid: Fin<L>) {
+        let mid = usize::from(mid.into_inner());
+        if mid > self.1.len() {
+            // SAFETY: mid < L
+            unsafe {core::hint::unreachable_unchecked()}
+        }
+        self.1.rotate_left(mid)
+    }
+
+    /// Split into the first mid elements and the rest
+    pub fn split_at<M: Term<Type = L::Type>>(&self, mid: Value<M>, _le: ValueLe<M, L>) -> DSliceSplit<'_, T, L, M> {
+        // SAFETY: mid <= L, so the halves have lengths mid and L - mid
+        unsafe {
+            let mid = usize::from(mid.into_inner());
+            (DSlice::new_ref_unchecked(self.1.get_unchecked(..mid)), DSlice::new_ref_unchecked(self.1.get_unchecked(mid..)))
+        }
+    }
+
+    /// Split into the first mid elements and the rest
+    pub fn split_at_mut<M: Term<Type = L::Type>>(&mut self, mid: Value<M>, _le: ValueLe<M, L>) -> DSliceSplitMut<'_, T, L, M> {
+        // SAFETY: mid <= L, so the halves have lengths mid and L - mid
+        unsafe {
+            let mid = usize::from(mid.into_inner());
+            let len = self.1.len();
+            let ptr = self.1.as_mut_ptr();
+            (
+                DSlice::new_mut_unchecked(core::slice::from_raw_parts_mut(ptr, mid)),
+                DSlice::new_mut_unchecked(core::slice::from_raw_parts_mut(ptr.add(mid), len - mid))
+            )
+        }
+    }
+
+    /// Subslice [A, B) of a slice of length at least B
+    pub fn range<A: Term<Type = L::Type>, B: Term<Type = L::Type>>(&self, range: DRange<A, B>, _le: ValueLe<B, L>) -> &DSlice<T, Sub<B, A>> {
+        // SAFETY: A <= B <= L
+        unsafe {DSlice::new_ref_unchecked(self.1.get_unchecked(usize::from(range.start().into_inner())..usize::from(range.end().into_inner())))}
+    }
+
+    /// Subslice [A, B) of a slice of length at least B
+    pub fn range_mut<A: Term<Type = L::Type>, B: Term<Type = L::Type>>(&mut self, range: DRange<A, B>, _le: ValueLe<B, L>) -> &mut DSlice<T, Sub<B, A>> {
+        // SAFETY: A <= B <= L
+        unsafe {DSlice::new_mut_unchecked(self.1.get_unchecked_mut(usize::from(range.start().into_inner())..usize::from(range.end().into_inner())))}
+    }
+
+    /// Theorem: the halves returned by split_at have total length L
+    pub fn split_at_join<M: Term<Type = L::Type>>() -> ValueEq<Add<M, Sub<L, M>>, L> {
+        b_plus_sub_a_b_eq_a()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, L: Term> DSlice<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    /// Apply f to all elements, collecting the results into a DVec of the same length
+    pub fn map_to_dvec<U>(&self, len: Value<L>, f: impl FnMut(&T) -> U) -> DVec<U, L> {
+        let v = self.1.iter().map(f).collect();
+        unsafe {DVec::new_unchecked(v, len)}
+    }
+}
+
+macro_rules! impl_chunks_iter {
+    ($S:ident $F:ident $new:ident $from_raw_parts:ident $($m:tt)*) => {
+        /// Iterator over L consecutive chunks of C elements
+        #[repr(C)]
+        pub struct $S<'a, T, L: Term, C: Term<Type = L::Type>>
+        {
+            ptr: NonNull<T>,
+            cols: Value<C>,
+            _marker: PhantomData<(&'a $($m)* T, Value<L>)>
+        }
+
+        pub struct $F<'a, T, LT, C>(PhantomData<fn(LT, C) -> &'a T>);
+
+        unsafe impl<'a, T, LT: UInt, C: Term<Type = LT> + 'a> DLIterFamily for $F<'a, T, LT, C>
+            where usize: From<LT> {
+            type LengthType = LT;
+            type Iter<L: Term<Type = Self::LengthType>> = $S<'a, T, L, C>;
+            type Item = &'a $($m)* DSlice<T, C>;
+
+            fn next<L: Term<Type = Self::LengthType>>(
+                iter: Self::Iter<Succ<L>>,
+                _value: Value<L>
+            ) -> (Self::Iter<L>, Self::Item) {
+                let ptr = iter.ptr.as_ptr();
+                let cols = usize::from(iter.cols.clone().into_inner());
+                // SAFETY: at least one more chunk of C elements follows ptr
+                let chunk = unsafe {DSlice::$new(core::slice::$from_raw_parts(ptr, cols))};
+                let next = unsafe {NonNull::new_unchecked(ptr.add(cols))};
+                ($S {ptr: next, cols: iter.cols, _marker: PhantomData}, chunk)
+            }
+
+            fn finish(_iter: Self::Iter<Zero<Self::LengthType>>) {
+            }
+        }
+
+        impl<'a, T, L: Term, C: Term<Type = L::Type> + 'a> DLIter for $S<'a, T, L, C>
+            where usize: From<L::Type>, L::Type: UInt {
+            type LengthType = L::Type;
+            type Length = L;
+            type Family = $F<'a, T, L::Type, C>;
+
+            fn drop(self, _len: Value<L>) {
+            }
+        }
+    }
+}
+
+impl_chunks_iter! {DChunksExact DChunksExactFamily new_ref_unchecked from_raw_parts}
+impl_chunks_iter! {DChunksExactMut DChunksExactMutFamily new_mut_unchecked from_raw_parts_mut mut}
+
+impl<T, R: Term, C: Term<Type = R::Type>> DSlice<T, Mul<R, C>>
+    where usize: From<R::Type>, R::Type: UInt {
+    /// Iterate over the R chunks of C elements
+    pub fn chunks_exact(&self, cols: Value<C>) -> DChunksExact<'_, T, R, C> {
+        DChunksExact {ptr: NonNull::from(&self.1).cast(), cols, _marker: PhantomData}
+    }
+
+    /// Iterate over the R chunks of C elements
+    pub fn chunks_exact_mut(&mut self, cols: Value<C>) -> DChunksExactMut<'_, T, R, C> {
+        DChunksExactMut {ptr: NonNull::from(&mut self.1).cast(), cols, _marker: PhantomData}
+    }
+
+    /// Get the chunk of C elements at index row
+    pub fn as_rows(&self, cols: Value<C>, row: Fin<R>) -> &DSlice<T, C> {
+        let (row, cols) = (usize::from(row.into_inner()), usize::from(cols.into_inner()));
+        // SAFETY: row * C + C <= R * C
+        unsafe {DSlice::new_ref_unchecked(self.1.get_unchecked(row * cols..row * cols + cols))}
+    }
+
+    /// Get the chunk of C elements at index row
+    pub fn as_rows_mut(&mut self, cols: Value<C>, row: Fin<R>) -> &mut DSlice<T, C> {
+        let (row, cols) = (usize::from(row.into_inner()), usize::from(cols.into_inner()));
+        // SAFETY: row * C + C <= R * C
+        unsafe {DSlice::new_mut_unchecked(self.1.get_unchecked_mut(row * cols..row * cols + cols))}
+    }
+}
+
+impl<T, R: Term<Type = usize>, const N: usize> DSlice<[T; N], R> {
+    /// View as R * N elements
+    ///
+    /// The length is the term Mul<R, ConstUsize<N>> itself, so no ValueEq is returned: as_arrays takes it back with refl()
+    ///
+    /// Panics if R * N overflows usize, which can only happen if T is zero-sized
+    pub fn flatten(&self) -> &DSlice<T, Mul<R, ConstUsize<N>>> {
+        let len = self.1.len().checked_mul(N).expect("flattened length overflows usize");
+        // SAFETY: [T; N] is laid out as N consecutive T, so the R arrays are R * N consecutive T
+        unsafe {DSlice::new_ref_unchecked(core::slice::from_raw_parts(self.1.as_ptr() as *const T, len))}
+    }
+
+    /// View as R * N elements
+    ///
+    /// Panics if R * N overflows usize, which can only happen if T is zero-sized
+    pub fn flatten_mut(&mut self) -> &mut DSlice<T, Mul<R, ConstUsize<N>>> {
+        let len = self.1.len().checked_mul(N).expect("flattened length overflows usize");
+        // SAFETY: [T; N] is laid out as N consecutive T, so the R arrays are R * N consecutive T
+        unsafe {DSlice::new_mut_unchecked(core::slice::from_raw_parts_mut(self.1.as_mut_ptr() as *mut T, len))}
+    }
+}
+
+impl<T, L: Term<Type = usize>> DSlice<T, L> {
+    /// View as R arrays of N elements, the inverse of flatten
+    pub fn as_arrays<R: Term<Type = usize>, const N: usize>(&self, rows: Value<R>, _eq: ValueEq<L, Mul<R, ConstUsize<N>>>) -> &DSlice<[T; N], R> {
+        // SAFETY: the slice holds R * N elements
+        unsafe {DSlice::new_ref_unchecked(core::slice::from_raw_parts(self.1.as_ptr() as *const [T; N], rows.into_inner()))}
+    }
+
+    /// View as R arrays of N elements, the inverse of flatten_mut
+    pub fn as_arrays_mut<R: Term<Type = usize>, const N: usize>(&mut self, rows: Value<R>, _eq: ValueEq<L, Mul<R, ConstUsize<N>>>) -> &mut DSlice<[T; N], R> {
+        // SAFETY: the slice holds R * N elements
+        unsafe {DSlice::new_mut_unchecked(core::slice::from_raw_parts_mut(self.1.as_mut_ptr() as *mut [T; N], rows.into_inner()))}
+    }
+}
+
+/// Iterator over L overlapping windows of W elements
+#[repr(C)]
+pub struct DWindows<'a, T, L: Term, W: Term<Type = L::Type>>
+{
+    ptr: NonNull<T>,
+    w: Value<W>,
+    _marker: PhantomData<(&'a T, Value<L>)>
+}
+
+pub struct DWindowsFamily<'a, T, LT, W>(PhantomData<fn(LT, W) -> &'a T>);
+
+unsafe impl<'a, T, LT: UInt, W: Term<Type = LT> + 'a> DLIterFamily for DWindowsFamily<'a, T, LT, W>
+    where usize: From<LT> {
+    type LengthType = LT;
+    type Iter<L: Term<Type = Self::LengthType>> = DWindows<'a, T, L, W>;
+    type Item = &'a DSlice<T, W>;
+
+    fn next<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        _value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        let ptr = iter.ptr.as_ptr();
+        // SAFETY: at least W elements follow ptr, and W > 0 so there is a next element if L > 0
+        let window = unsafe {DSlice::new_ref_unchecked(core::slice::from_raw_parts(ptr, usize::from(iter.w.clone().into_inner())))};
+        (DWindows {ptr: unsafe {NonNull::new_unchecked(ptr.add(1))}, w: iter.w, _marker: PhantomData}, window)
+    }
+
+    fn finish(_iter: Self::Iter<Zero<Self::LengthType>>) {
+    }
+}
+
+impl<'a, T, L: Term, W: Term<Type = L::Type> + 'a> DLIter for DWindows<'a, T, L, W>
+    where usize: From<L::Type>, L::Type: UInt {
+    type LengthType = L::Type;
+    type Length = L;
+    type Family = DWindowsFamily<'a, T, L::Type, W>;
+
+    fn drop(self, _len: Value<L>) {
+    }
+}
+
+impl<T, L: Term> DSlice<T, L>
+    where L::Type: UInt {
+    /// Iterate over all L - W + 1 windows of W consecutive elements
+    pub fn windows<W: Term<Type = L::Type>>(&self, w: Value<W>, _le: ValueLe<W, L>, _gt: ValueGt<W, Zero<L::Type>>) -> DWindows<'_, T, Succ<Sub<L, W>>, W> {
+        DWindows {ptr: NonNull::from(&self.1).cast(), w, _marker: PhantomData}
+    }
+}
+
+/// Reference to a DSlice, whose length is read from the slice
+pub struct DSliceRef<'x, T, L: Term>(&'x DSlice<T, L>);
+
+/// Mutable reference to a DSlice, whose length is read from the slice
+pub struct DSliceMut<'x, T, L: Term>(&'x mut DSlice<T, L>);
+
+impl<'x, T, L: Term> Clone for DSliceRef<'x, T, L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'x, T, L: Term> Copy for DSliceRef<'x, T, L> {}
+
+impl<'x, T, L: Term> DSliceRef<'x, T, L> {
+    pub fn new(slice: &'x DSlice<T, L>) -> Self {
+        DSliceRef(slice)
+    }
+
+    pub fn into_inner(self) -> &'x DSlice<T, L> {
+        self.0
+    }
+}
+
+impl<'x, T, L: Term> DSliceMut<'x, T, L> {
+    pub fn new(slice: &'x mut DSlice<T, L>) -> Self {
+        DSliceMut(slice)
+    }
+
+    pub fn into_inner(self) -> &'x mut DSlice<T, L> {
+        self.0
+    }
+}
+
+impl<'x, T, L: Term> DSliceRef<'x, T, L>
+    where usize: From<L::Type>, L::Type: UInt + TryFrom<usize> {
+    pub fn len(&self) -> Value<L> {
+        self.0.len()
+    }
+
+    pub fn as_slice(&self) -> &'x [T] {
+        self.0.as_slice()
+    }
+
+    pub fn iter(&self) -> DIter<DSliceIter<'x, T, L>> {
+        DIter::new(self.0.iter(), self.len())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_dvec(&self) -> DVec<T, L>
+        where T: Clone {
+        unsafe {DVec::new_unchecked(self.as_slice().to_vec(), self.len())}
+    }
+}
+
+impl<'x, T, L: Term> DSliceMut<'x, T, L>
+    where usize: From<L::Type>, L::Type: UInt + TryFrom<usize> {
+    pub fn len(&self) -> Value<L> {
+        self.0.len()
+    }
+
+    pub fn as_slice(&self) -> &[T] {
+        self.0.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.0.as_mut_slice()
+    }
+
+    pub fn as_dslice_ref(&self) -> DSliceRef<'_, T, L> {
+        DSliceRef(self.0)
+    }
+
+    pub fn iter(&self) -> DIter<DSliceIter<'_, T, L>> {
+        DIter::new(self.0.iter(), self.len())
+    }
+
+    pub fn iter_mut(&mut self) -> DIter<DSliceIterMut<'_, T, L>> {
+        let len = self.len();
+        DIter::new(self.0.iter_mut(), len)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn to_dvec(&self) -> DVec<T, L>
+        where T: Clone {
+        unsafe {DVec::new_unchecked(self.as_slice().to_vec(), self.len())}
+    }
+}
+
+impl<'x, T, L: Term> Deref for DSliceRef<'x, T, L> {
+    type Target = DSlice<T, L>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'x, T, L: Term> Deref for DSliceMut<'x, T, L> {
+    type Target = DSlice<T, L>;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'x, T, L: Term> DerefMut for DSliceMut<'x, T, L> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+impl<'x, T: core::fmt::Debug, L: Term> core::fmt::Debug for DSliceRef<'x, T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        core::fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<'x, T: core::fmt::Debug, L: Term> core::fmt::Debug for DSliceMut<'x, T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        core::fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T, L: Term> DSlice<T, L>
+    where L::Type: UInt + TryFrom<usize> {
+    /// Binary search a sorted slice, returning the index of a match or the index where x could be inserted
+    pub fn binary_search(&self, x: &T) -> Result<Fin<L>, Fin<Succ<L>>>
+        where T: Ord {
+        self.binary_search_by(|y| y.cmp(x))
+    }
+
+    /// Binary search a slice sorted by f
+    ///
+    /// Panics if the slice is not sorted and std returns an out of bounds index
+    pub fn binary_search_by(&self, f: impl FnMut(&T) -> core::cmp::Ordering) -> Result<Fin<L>, Fin<Succ<L>>> {
+        let len = self.len();
+        match self.1.binary_search_by(f) {
+            Ok(i) => Ok(L::Type::try_from(i).ok().and_then(|i| Fin::from(len, i)).expect("binary search returned an out of bounds index")),
+            Err(i) => Err(L::Type::try_from(i).ok().and_then(|i| Fin::from(Succ(len), i)).expect("binary search returned an out of bounds index")),
+        }
+    }
+
+    /// Index of the first element for which pred is false, assuming the slice is partitioned by pred
+    ///
+    /// Panics if the slice is not partitioned and std returns an out of bounds index
+    pub fn partition_point(&self, pred: impl FnMut(&T) -> bool) -> Fin<Succ<L>> {
+        let len = self.len();
+        L::Type::try_from(self.1.partition_point(pred)).ok().and_then(|i| Fin::from(Succ(len), i)).expect("partition point out of bounds")
+    }
+
+}
+
+#[cfg(feature = "std")]
+impl<T, L: Term> DSlice<T, L> {
+    pub fn sort(&mut self) -> Sorted<'_, T, L>
+        where T: Ord {
+        self.1.sort();
+        Sorted(DSliceRef(self))
+    }
+
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) -> SortedByKey<'_, T, L, F> {
+        self.1.sort_by_key(&mut f);
+        SortedByKey(DSliceRef(self), f)
+    }
+}
+
+/// Slice known to be sorted, which stays sorted for as long as it is borrowed
+pub struct Sorted<'x, T, L: Term>(DSliceRef<'x, T, L>);
+
+impl<'x, T, L: Term> Sorted<'x, T, L>
+    where L::Type: UInt + TryFrom<usize> {
+    pub fn into_inner(self) -> DSliceRef<'x, T, L> {
+        self.0
+    }
+
+    pub fn binary_search(&self, x: &T) -> Result<Fin<L>, Fin<Succ<L>>>
+        where T: Ord {
+        self.0.binary_search(x)
+    }
+
+    /// Index of the first element not less than x
+    pub fn lower_bound(&self, x: &T) -> Fin<Succ<L>>
+        where T: Ord {
+        self.0.partition_point(|y| y < x)
+    }
+}
+
+impl<'x, T, L: Term> Deref for Sorted<'x, T, L> {
+    type Target = DSliceRef<'x, T, L>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Slice known to be sorted by the key function F, which stays sorted for as long as it is borrowed
+pub struct SortedByKey<'x, T, L: Term, F>(DSliceRef<'x, T, L>, F);
+
+impl<'x, T, L: Term, K: Ord, F: FnMut(&T) -> K> SortedByKey<'x, T, L, F>
+    where L::Type: UInt + TryFrom<usize> {
+    pub fn into_inner(self) -> (DSliceRef<'x, T, L>, F) {
+        (self.0, self.1)
+    }
+
+    pub fn binary_search(&mut self, key: &K) -> Result<Fin<L>, Fin<Succ<L>>> {
+        let f = &mut self.1;
+        self.0.binary_search_by(|y| f(y).cmp(key))
+    }
+}
+
+impl<'x, T, L: Term, F> Deref for SortedByKey<'x, T, L, F> {
+    type Target = DSliceRef<'x, T, L>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
@@ -110,12 +639,205 @@ impl<'a, T> DSlice<T, Var<'a, usize>>
 
 impl<'a, T, const N: usize> From<&'a [T; N]> for &'a DSlice<T, ConstUsize<N>> {
     fn from(x: &'a [T; N]) -> Self {
-        unsafe {core::mem::transmute(x)}
+        unsafe {DSlice::new_ref_unchecked(x)}
     }
 }
 
 impl<'a, T, const N: usize> From<&'a mut [T; N]> for &'a mut DSlice<T, ConstUsize<N>> {
     fn from(x: &'a mut [T; N]) -> Self {
-        unsafe {core::mem::transmute(x)}
+        unsafe {DSlice::new_mut_unchecked(x)}
     }
 }
+
+#[test]
+pub fn test_split_at() {
+    use crate::guard::make_guard;
+    use crate::term::value_le;
+    use crate::transmutable::coerce;
+
+    let mut a = [1, 2, 3, 4, 5];
+    make_guard!(g);
+    let (s, len) = DSlice::new_mut(g, &mut a);
+    let mid = ConstUsize::<2>();
+    let le = value_le(mid, len).unwrap();
+    let (l, r) = s.split_at_mut(mid, le);
+    let rlen = Sub(len, mid);
+    l[Fin::from(mid, 1).unwrap()] = 7;
+    r[Fin::from(rlen, 0).unwrap()] = 8;
+    let (l, r) = s.split_at(mid, le);
+    assert_eq!(l.as_slice(), &[1, 7]);
+    assert_eq!(r.as_slice(), &[8, 4, 5]);
+    let total = coerce(Add(mid, rlen), DSlice::<u32, _>::split_at_join());
+    assert_eq!(total, len);
+}
+
+#[test]
+pub fn test_chunks_exact() {
+    use crate::iter::DIter;
+
+    let mut a = [[1, 2, 3], [4, 5, 6]];
+    let rows = ConstUsize::<2>();
+    let cols = ConstUsize::<3>();
+    let s: &mut DSlice<[i32; 3], _> = (&mut a).into();
+    let flat = s.flatten_mut();
+    let it = DIter::new(flat.chunks_exact_mut(cols), rows);
+    let (it, row) = it.next().ok().unwrap();
+    row[Fin::from(cols, 0).unwrap()] = 7;
+    let (it, row) = it.next().ok().unwrap();
+    row[Fin::from(cols, 2).unwrap()] = 8;
+    assert!(it.next().is_err());
+
+    let row = flat.as_rows(cols, Fin::from(rows, 1).unwrap());
+    assert_eq!(row.as_slice(), &[4, 5, 8]);
+    let arrays = flat.as_arrays::<_, 3>(rows, ValueEq::refl());
+    assert_eq!(arrays.as_slice(), &[[7, 2, 3], [4, 5, 8]]);
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_flatten_overflow() {
+    extern crate std;
+
+    let a = [[(); usize::MAX]; 2];
+    let s: &DSlice<[(); usize::MAX], _> = (&a).into();
+    assert!(std::panic::catch_unwind(|| s.flatten().len()).is_err());
+}
+
+#[test]
+pub fn test_windows() {
+    use crate::guard::make_guard;
+    use crate::iter::DIter;
+    use crate::term::{value_le, value_lt};
+
+    let a = [1, 2, 3, 4];
+    make_guard!(g);
+    let (s, len) = DSlice::new_ref(g, &a);
+    let w = ConstUsize::<3>();
+    let le = value_le(w, len).unwrap();
+    let gt = value_lt(Zero(), w).unwrap();
+    let count = Succ(Sub(len, w));
+    assert_eq!(count.into_inner(), 2);
+    let it = DIter::new(s.windows(w, le, gt), count);
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(x.as_slice(), &[1, 2, 3]);
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(x.as_slice(), &[2, 3, 4]);
+    assert!(it.next().is_err());
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_sort_search() {
+    use crate::guard::make_guard;
+
+    let mut a = [5, 1, 4, 2];
+    make_guard!(g);
+    let (s, _) = DSlice::new_mut(g, &mut a);
+    let sorted = s.sort();
+    assert_eq!(sorted.as_slice(), &[1, 2, 4, 5]);
+    let i = sorted.binary_search(&4).unwrap();
+    assert_eq!(sorted[i], 4);
+    assert_eq!(Fin::into_inner(sorted.binary_search(&3).unwrap_err()), 2);
+    assert_eq!(Fin::into_inner(sorted.lower_bound(&6)), 4);
+
+    let mut sorted = s.sort_by_key(|x: &i32| -x);
+    assert_eq!(sorted.as_slice(), &[5, 4, 2, 1]);
+    assert_eq!(Fin::into_inner(sorted.binary_search(&-2).unwrap()), 2);
+    assert_eq!(Fin::into_inner(s.partition_point(|x| *x > 3)), 2);
+
+    // the sort is stable and the length may have any unsigned type
+    let mut b = [(1, 'a'), (0, 'b'), (1, 'c'), (0, 'd')];
+    let s: &mut DSlice<_, crate::int::ConstU8<4>> = unsafe {DSlice::new_mut_unchecked(&mut b)};
+    assert_eq!(s.len().into_inner(), 4u8);
+    let sorted = s.sort_by_key(|x| x.0);
+    assert_eq!(sorted.as_slice(), &[(0, 'b'), (0, 'd'), (1, 'a'), (1, 'c')]);
+}
+
+#[test]
+pub fn test_index_iter() {
+    use crate::iter::DIter;
+
+    let mut a = [1, 2, 3];
+    let n = ConstUsize::<3>();
+    let s: &mut DSlice<i32, _> = (&mut a).into();
+    s[Fin::from(n, 2).unwrap()] += 10;
+    let it = DIter::new(s.iter_mut(), n);
+    let (it, x) = it.next().ok().unwrap();
+    *x += 20;
+    let (it, _) = it.next().ok().unwrap();
+    let (it, x) = it.next().ok().unwrap();
+    *x += 30;
+    assert!(it.next().is_err());
+    let it = DIter::new(s.iter(), n);
+    let (it, _) = it.next().ok().unwrap();
+    let (it, _) = it.next().ok().unwrap();
+    let (_, x) = it.next().ok().unwrap();
+    assert_eq!(*x, 43);
+    assert_eq!(s.as_slice(), &[21, 2, 43]);
+}
+
+#[test]
+pub fn test_in_place() {
+    let n = ConstUsize::<4>();
+    let i = |x| Fin::from(n, x).unwrap();
+    let mut a = [1, 2, 3, 4];
+    let s: &mut DSlice<i32, _> = (&mut a).into();
+    s.swap(i(0), i(3));
+    assert_eq!(s.as_slice(), &[4, 2, 3, 1]);
+    s.rotate_left(i(1));
+    assert_eq!(s.as_slice(), &[2, 3, 1, 4]);
+    s.reverse();
+    assert_eq!(s.as_slice(), &[4, 1, 3, 2]);
+    let b = [5, 6, 7, 8];
+    s.copy_from_dslice((&b).into());
+    assert_eq!(s.as_slice(), &b);
+    s.fill(0);
+    assert_eq!(s.as_slice(), &[0; 4]);
+
+    let mut c = [None, None];
+    let d = [Some(1), Some(2)];
+    let c: &mut DSlice<Option<i32>, _> = (&mut c).into();
+    c.clone_from_dslice((&d).into());
+    assert_eq!(c.as_slice(), &d);
+}
+
+#[test]
+pub fn test_rev() {
+    use crate::iter::DIter;
+
+    let mut a = [1, 2, 3];
+    let n = ConstUsize::<3>();
+    let s: &mut DSlice<i32, _> = (&mut a).into();
+    let it = DIter::new(s.iter_mut(), n);
+    let (it, x) = it.next_back().ok().unwrap();
+    *x = 30;
+    let (it, x) = it.next().ok().unwrap();
+    *x = 10;
+    let (it, x) = it.next_back().ok().unwrap();
+    *x = 20;
+    assert!(it.next_back().is_err());
+    let it = DIter::new(s.iter(), n).rev();
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(*x, 30);
+    let (it, x) = it.next_back().ok().unwrap();
+    assert_eq!(*x, 10);
+    let (_, x) = it.next().ok().unwrap();
+    assert_eq!(*x, 20);
+}
+
+#[test]
+pub fn test_iter_enumerated() {
+    use crate::iter::DIter;
+
+    let a = [5, 6, 7];
+    let n = ConstUsize::<3>();
+    let s: &DSlice<i32, _> = (&a).into();
+    let it = DIter::new(s.iter_enumerated(), n);
+    let (it, (i, x)) = it.next().ok().unwrap();
+    assert_eq!((Fin::into_inner(i), *x), (0, 5));
+    let (it, (i, x)) = it.next_back().ok().unwrap();
+    assert_eq!((Fin::into_inner(i), *x), (2, 7));
+    let (it, (i, x)) = it.next().ok().unwrap();
+    assert_eq!(s[i], *x);
+    assert!(it.next().is_err());
+}
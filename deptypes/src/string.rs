@@ -0,0 +1,182 @@
+use crate::fin::Fin;
+use crate::guard::Guard;
+use crate::int::Succ;
+use crate::ops::Sub;
+use crate::slice::DSlice;
+use crate::term::{Term, Value};
+use crate::var::Var;
+
+#[cfg(feature = "std")]
+use core::ops::Deref;
+#[cfg(feature = "std")]
+use crate::{int::Zero, ops::Add, vec::DVec};
+#[cfg(feature = "std")]
+use alloc::string::String;
+
+/// UTF-8 string slice whose length in bytes is L
+///
+/// Like DSlice, it is dynamically sized, so references to it carry the runtime length as well
+#[repr(transparent)]
+pub struct DStr<L: Term<Type = usize>>(DSlice<u8, L>);
+
+/// A byte index of a DStr that has been checked to be at a char boundary
+///
+/// It keeps the borrow of the DStr it was checked against, so it can't be used to split another string with the same length term
+pub struct CharBoundary<'x, L: Term<Type = usize>> {
+    s: &'x DStr<L>,
+    idx: Fin<Succ<L>>,
+}
+
+/// Result of CharBoundary::split_at: the prefix, the suffix and the length of the prefix
+pub type DStrSplit<'x, 'a, L> = (&'x DStr<Var<'a, usize>>, &'x DStr<Sub<L, Var<'a, usize>>>, Value<Var<'a, usize>>);
+
+impl<L: Term<Type = usize>> DStr<L> {
+    /// # Safety
+    ///
+    /// x must be valid UTF-8 and have length L
+    pub unsafe fn from_dslice_unchecked(x: &DSlice<u8, L>) -> &Self {
+        &*(x as *const DSlice<u8, L> as *const DStr<L>)
+    }
+
+    /// # Safety
+    ///
+    /// x must have length L
+    pub unsafe fn new_ref_unchecked(x: &str) -> &Self {
+        Self::from_dslice_unchecked(DSlice::new_ref_unchecked(x.as_bytes()))
+    }
+
+    pub fn as_bytes(&self) -> &DSlice<u8, L> {
+        &self.0
+    }
+
+    pub fn len(&self) -> Value<L> {
+        self.0.len()
+    }
+
+    pub fn as_str(&self) -> &str {
+        unsafe {core::str::from_utf8_unchecked(self.0.as_slice())}
+    }
+
+    /// Check that idx is at a char boundary, i.e. that it is the length or that it is the start of a UTF-8 sequence
+    pub fn char_boundary(&self, idx: Fin<Succ<L>>) -> Option<CharBoundary<'_, L>> {
+        if self.as_str().is_char_boundary(idx.into_inner()) {
+            Some(CharBoundary {s: self, idx})
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> DStr<Var<'a, usize>> {
+    pub fn new_ref<'x>(guard: Guard<'a>, x: &'x str) -> (&'x DStr<Var<'a, usize>>, Value<Var<'a, usize>>) {
+        let len = x.len();
+        (unsafe {DStr::new_ref_unchecked(x)}, Var(guard, len))
+    }
+}
+
+impl<'x, L: Term<Type = usize>> CharBoundary<'x, L> {
+    pub fn index(&self) -> Fin<Succ<L>> {
+        self.idx
+    }
+
+    pub fn dstr(&self) -> &'x DStr<L> {
+        self.s
+    }
+
+    /// Split the string at the boundary, into a prefix of length M and a suffix of length L - M
+    ///
+    /// The lengths add up to L by `int::sub::b_plus_sub_a_b_eq_a`
+    pub fn split_at<'a>(self, guard: Guard<'a>) -> DStrSplit<'x, 'a, L> {
+        let mid = self.idx.into_inner();
+        // SAFETY: mid <= L and it is at a char boundary
+        let bytes = self.s.0.as_slice();
+        let (a, b) = unsafe {(bytes.get_unchecked(..mid), bytes.get_unchecked(mid..))};
+        unsafe {(
+            DStr::new_ref_unchecked(core::str::from_utf8_unchecked(a)),
+            DStr::new_ref_unchecked(core::str::from_utf8_unchecked(b)),
+            Var(guard, mid),
+        )}
+    }
+}
+
+/// UTF-8 string whose length in bytes is L
+#[cfg(feature = "std")]
+#[repr(transparent)]
+pub struct DString<L: Term<Type = usize>>(DVec<u8, L>);
+
+#[cfg(feature = "std")]
+impl DString<Zero<usize>> {
+    pub fn new() -> Self {
+        DString(DVec::new())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for DString<Zero<usize>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> DString<Var<'a, usize>> {
+    pub fn from(guard: Guard<'a>, s: String) -> Self {
+        DString(DVec::from(guard, s.into_bytes()))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<L: Term<Type = usize>> DString<L> {
+    pub fn len(&self) -> Value<L> {
+        self.0.len()
+    }
+
+    pub fn as_str(&self) -> &str {
+        (**self).as_str()
+    }
+
+    pub fn into_bytes(self) -> DVec<u8, L> {
+        self.0
+    }
+
+    pub fn into_string(self) -> String {
+        unsafe {String::from_utf8_unchecked(self.0.into_vec())}
+    }
+
+    pub fn push_str<M: Term<Type = usize>>(self, s: &DStr<M>) -> DString<Add<L, M>> {
+        let len = Add(self.len(), s.len());
+        let mut v = self.0.into_vec();
+        v.extend_from_slice(s.as_str().as_bytes());
+        DString(unsafe {DVec::new_unchecked(v, len)})
+    }
+}
+
+#[cfg(feature = "std")]
+impl<L: Term<Type = usize>> Deref for DString<L> {
+    type Target = DStr<L>;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe {DStr::from_dslice_unchecked(&self.0)}
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+pub fn test_dstring() {
+    use crate::guard::make_guard;
+    use alloc::string::ToString;
+
+    make_guard!(g);
+    let s = DString::from(g, "héllo".to_string());
+    let len = s.len();
+    assert!(s.char_boundary(Fin::from(Succ(len), 2).unwrap()).is_none());
+    let b = s.char_boundary(Fin::from(Succ(len), 3).unwrap()).unwrap();
+    make_guard!(g);
+    let (a, rest, mid) = b.split_at(g);
+    assert_eq!(a.as_str(), "hé");
+    assert_eq!(rest.as_str(), "llo");
+    assert_eq!(rest.len(), Sub(len, mid));
+    let t = DString::new().push_str(rest).push_str(a);
+    assert_eq!(t.as_str(), "llohé");
+    assert_eq!(t.as_bytes()[Fin::from(t.len(), 0).unwrap()], b'l');
+}
@@ -13,9 +13,9 @@ impl<T, U> Equiv<T, U> {
     #[inline(always)]
     pub fn coerce(self, t: T) -> U
     {
-        let u = unsafe {core::mem::transmute_copy(&t)};
-        core::mem::forget(t);
-        u
+        // t must not be moved after the copy, since that would invalidate any mutable references in u
+        let t = core::mem::ManuallyDrop::new(t);
+        unsafe {core::mem::transmute_copy(&*t)}
     }
 }
 
@@ -24,9 +24,9 @@ impl<T, U> Transm<T, U> {
     #[inline(always)]
     pub fn coerce(self, t: T) -> U
     {
-        let u = unsafe {core::mem::transmute_copy(&t)};
-        core::mem::forget(t);
-        u
+        // t must not be moved after the copy, since that would invalidate any mutable references in u
+        let t = core::mem::ManuallyDrop::new(t);
+        unsafe {core::mem::transmute_copy(&*t)}
     }
 }
 
@@ -1,22 +1,25 @@
-use crate::guard::Guard;
+use crate::guard::{make_guard, Guard};
 use crate::kinds::Term2S;
 use crate::int::uint::UInt;
-use crate::int::{One, Succ, Zero};
+use crate::int::{One, Pred, Succ, Zero};
 use crate::loops::repeat_to_zero;
+use crate::int::add::add_0_a_eq_a;
 use crate::int::sub::{a_minus_0_eq_a, a_plus_b_minus_b_eq_a, s_a_minus_s_0_eq_a, s_sub_a_s_b_eq_a_minus_b};
-use crate::unreachable::UnreachableUnchecked;
+use crate::pair::DPair;
+use core::alloc::Layout;
 use core::marker::PhantomData;
 use core::mem::ManuallyDrop;
 use core::ops::Deref;
 use core::ops::DerefMut;
-use crate::term::ValueEq;
+use core::ptr::NonNull;
+use crate::term::{ValueEq, ValueLe, ValueLt};
 use crate::transmutable::{coerce, Equiv, Transm};
 
 use crate::term::{Term, Value};
-use crate::var::Var;
+use crate::var::{Erasure, Var};
 use crate::fin::Fin;
-use crate::slice::DSlice;
-use crate::iter::{DIterBridge, DLIter, DLIterFamily};
+use crate::slice::{DSlice, DSliceMut, DSliceRef};
+use crate::iter::{DLIter, DLIterFamily, DLIterFamilyBack, FromDLIter};
 use crate::ops::{Add, Sub};
 
 use alloc::vec::Vec;
@@ -36,19 +39,13 @@ impl<T, L: Term> DLVec<T, L>
     pub fn with_len(self, len: Value<L>) -> DVec<T, L> {
         DVec(ManuallyDrop::new(self), len)
     }
-}
-
-impl<T, L: Term> Deref for DLVec<T, L> {
-    type Target = DSlice<T, L>;
 
-    fn deref(&self) -> &Self::Target {
-        unsafe {DSlice::new_ref_unchecked(core::slice::from_raw_parts(self.0, 0))}
+    pub fn dslice(&self, len: Value<L>) -> &DSlice<T, L> {
+        unsafe {DSlice::new_ref_unchecked(core::slice::from_raw_parts(self.0, usize::from(len.into_inner())))}
     }
-}
 
-impl<T, L: Term> DerefMut for DLVec<T, L> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe {DSlice::new_mut_unchecked(core::slice::from_raw_parts_mut(self.0, 0))}
+    pub fn dslice_mut(&mut self, len: Value<L>) -> &mut DSlice<T, L> {
+        unsafe {DSlice::new_mut_unchecked(core::slice::from_raw_parts_mut(self.0, usize::from(len.into_inner())))}
     }
 }
 
@@ -112,6 +109,19 @@ impl<T, L: Term> DVec<T, L>
         self.1.clone()
     }
 
+    /// The DVec without its length, which used to be the Deref target
+    pub fn as_dlvec(&self) -> &DLVec<T, L> {
+        &self.0
+    }
+
+    pub fn as_dslice(&self) -> DSliceRef<'_, T, L> {
+        DSliceRef::new(self)
+    }
+
+    pub fn as_dslice_mut(&mut self) -> DSliceMut<'_, T, L> {
+        DSliceMut::new(self)
+    }
+
     pub fn push(self, x: T) -> DVec<T, Succ<L>> {
         let len = self.len();
         let mut v = self.into_vec();
@@ -119,9 +129,13 @@ impl<T, L: Term> DVec<T, L>
         unsafe {DVec::new_unchecked(v, Succ(len))}
     }
 
-    // TODO: provide our own dependent iterator
-    pub fn into_iter(self) -> DIterBridge<alloc::vec::IntoIter<T>, L, UnreachableUnchecked> {
-        DIterBridge::new_assert(self.into_vec().into_iter(), unsafe {UnreachableUnchecked::new()})
+    pub fn into_iter(self) -> DVecIntoIter<T, L> {
+        let (ptr, cap) = (self.0.0, self.0.1);
+        let len = usize::from(self.1.clone().into_inner());
+        core::mem::forget(self);
+        // SAFETY: vector buffers are never null
+        let ptr = unsafe {NonNull::new_unchecked(ptr)};
+        DVecIntoIter {buf: ptr, cap, ptr, remaining: len, _marker: PhantomData}
     }
 
     pub fn clear(self) -> DVec<T, Zero<L::Type>> {
@@ -130,6 +144,126 @@ impl<T, L: Term> DVec<T, L>
         unsafe {DVec::new_unchecked(v, Zero())}
     }
 
+    /// Insert an element at position idx, shifting all elements after it to the right
+    pub fn insert(self, idx: Fin<Succ<L>>, x: T) -> DVec<T, Succ<L>> {
+        let len = self.len();
+        let mut v = self.into_vec();
+        v.insert(usize::from(idx.into_inner()), x);
+        unsafe {DVec::new_unchecked(v, Succ(len))}
+    }
+
+    /// Remove the element at position idx, shifting all elements after it to the left
+    ///
+    /// Since a Fin<L> exists, L is not zero and thus Pred<L> is its predecessor
+    pub fn remove(self, idx: Fin<L>) -> (DVec<T, Pred<L>>, T) {
+        let len = self.len();
+        let mut v = self.into_vec();
+        let x = v.remove(usize::from(idx.into_inner()));
+        (unsafe {DVec::new_unchecked(v, Pred(len))}, x)
+    }
+
+    pub fn truncate<M: Term<Type = L::Type>>(self, len: Value<M>, _le: ValueLe<M, L>) -> DVec<T, M> {
+        let mut v = self.into_vec();
+        v.truncate(usize::from(len.clone().into_inner()));
+        unsafe {DVec::new_unchecked(v, len)}
+    }
+
+    /// Split the vector into the first `at` elements and the remaining ones
+    pub fn split_off<M: Term<Type = L::Type>>(self, at: Value<M>, _le: ValueLe<M, L>) -> (DVec<T, M>, DVec<T, Sub<L, M>>) {
+        let len = self.len();
+        let mut v = self.into_vec();
+        let tail = v.split_off(usize::from(at.clone().into_inner()));
+        let tail_len = Sub(len, at.clone());
+        (unsafe {DVec::new_unchecked(v, at)}, unsafe {DVec::new_unchecked(tail, tail_len)})
+    }
+
+    /// Same as add
+    pub fn append<BL: Term<Type = L::Type>>(self, bv: DVec<T, BL>) -> DVec<T, Add<L, BL>> {
+        self.add(bv)
+    }
+
+    /// Apply f to all elements, reusing the allocation if T and U have the same layout
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> DVec<U, L> {
+        let len = self.len();
+        if Layout::new::<T>() != Layout::new::<U>() {
+            let v: Vec<U> = self.into_vec().into_iter().map(f).collect();
+            return unsafe {DVec::new_unchecked(v, len)};
+        }
+
+        /// Drops the already mapped and the not yet mapped elements and frees the buffer if f panics
+        struct MapGuard<T, U> {ptr: *mut T, len: usize, cap: usize, done: usize, _marker: PhantomData<U>}
+
+        impl<T, U> Drop for MapGuard<T, U> {
+            fn drop(&mut self) {
+                unsafe {
+                    core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.ptr as *mut U, self.done));
+                    core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.ptr.add(self.done + 1), self.len - self.done - 1));
+                    drop(Vec::from_raw_parts(self.ptr, 0, self.cap));
+                }
+            }
+        }
+
+        let (ptr, cap) = (self.0.0, self.0.1);
+        let n = usize::from(len.clone().into_inner());
+        core::mem::forget(self);
+        let mut guard = MapGuard::<T, U> {ptr, len: n, cap, done: 0, _marker: PhantomData};
+        while guard.done < n {
+            unsafe {
+                let p = ptr.add(guard.done);
+                let y = f(p.read());
+                (p as *mut U).write(y);
+            }
+            guard.done += 1;
+        }
+        core::mem::forget(guard);
+        // SAFETY: the buffer has the same layout for T and U, and all n elements are now U values
+        unsafe {DVec::new_unchecked(Vec::from_raw_parts(ptr as *mut U, n, cap), len)}
+    }
+
+    /// Like map, but f also receives the index of the element
+    pub fn map_indexed<U>(self, mut f: impl FnMut(Fin<L>, T) -> U) -> DVec<U, L> {
+        let mut i: L::Type = num_traits::Zero::zero();
+        self.map(|x| {
+            let idx = i.clone();
+            i = i.clone() + num_traits::One::one();
+            // SAFETY: elements are visited in order, so the index is less than the length
+            f(unsafe {Fin::new_unchecked(idx)}, x)
+        })
+    }
+
+    pub fn zip<U>(self, other: DVec<U, L>) -> DVec<(T, U), L> {
+        let len = self.len();
+        let v = self.into_vec().into_iter().zip(other.into_vec()).collect();
+        unsafe {DVec::new_unchecked(v, len)}
+    }
+
+    /// Keep only the elements for which f returns true, returning the vector paired with its new length
+    pub fn retain(self, f: impl FnMut(&T) -> bool) -> DPair<L::Type, DVecFamily<T>>
+        where L::Type: TryFrom<usize> {
+        let mut v = self.into_vec();
+        v.retain(f);
+        Self::into_dpair(v)
+    }
+
+    /// Remove consecutive repeated elements, returning the vector paired with its new length
+    pub fn dedup(self) -> DPair<L::Type, DVecFamily<T>>
+        where L::Type: TryFrom<usize>, T: PartialEq {
+        let mut v = self.into_vec();
+        v.dedup();
+        Self::into_dpair(v)
+    }
+
+    /// The length of vec must fit in L::Type, which is the case if it's not longer than a vector with an L::Type length
+    fn into_dpair(vec: Vec<T>) -> DPair<L::Type, DVecFamily<T>>
+        where L::Type: TryFrom<usize> {
+        make_guard!(g);
+        // SAFETY: vec is not longer than a vector with an L::Type length, so its length fits
+        let len = Var(g, unsafe {L::Type::try_from(vec.len()).unwrap_unchecked()});
+        // SAFETY: len is the length of vec
+        let v = unsafe {DVec::new_unchecked(vec, len.clone())};
+        DPair::new(len, v)
+    }
+
     pub fn add_iter<I, IL: Term<Type = L::Type>>(
         self,
         iter: I,
@@ -170,8 +304,25 @@ impl<T, L: Term> DVec<T, L>
     
 }
 
+impl<T, I: DLIter> FromDLIter<I> for DVec<T, I::Length>
+    where usize: From<I::LengthType>, I::Family: DLIterFamily<Item = T> {
+    fn from_dliter(iter: I, len: Value<I::Length>) -> Self {
+        let vec = DVec::with_capacity(usize::from(len.clone().into_inner())).add_iter(iter, len);
+        coerce(vec, DVec::equiv(add_0_a_eq_a()))
+    }
+}
+
+impl<T, U, L: Term> DVec<(T, U), L>
+    where usize: From<L::Type>, L::Type: UInt {
+    pub fn unzip(self) -> (DVec<T, L>, DVec<U, L>) {
+        let len = self.len();
+        let (a, b) = self.into_vec().into_iter().unzip();
+        (unsafe {DVec::new_unchecked(a, len.clone())}, unsafe {DVec::new_unchecked(b, len)})
+    }
+}
+
 impl<T, L: Term> DVec<T, Succ<L>>
-    where usize: From<L::Type>, L::Type: UInt + From<usize> {
+    where usize: From<L::Type>, L::Type: UInt {
     pub fn swap_remove<'a>(self, idx: Fin<L>) -> (DVec<T, L>, T) {
         let len = self.len();
         let mut v = self.into_vec();
@@ -180,6 +331,107 @@ impl<T, L: Term> DVec<T, Succ<L>>
         let len = coerce(len, s_a_minus_s_0_eq_a());
         (unsafe {DVec::new_unchecked(v, len)}, x)
     }
+
+    pub fn pop(self) -> (DVec<T, L>, T) {
+        let len = self.len();
+        let mut v = self.into_vec();
+        // SAFETY: the length is a successor and thus not zero
+        let x = unsafe {v.pop().unwrap_unchecked()};
+        let len = coerce(Pred(len), s_a_minus_s_0_eq_a());
+        (unsafe {DVec::new_unchecked(v, len)}, x)
+    }
+}
+
+/// Owning iterator over the elements of a DVec, reading them directly from its buffer
+///
+/// The number of remaining elements is also kept at runtime, so that dropping the iterator can drop them and free the buffer
+#[repr(C)]
+pub struct DVecIntoIter<T, L: Term> {
+    buf: NonNull<T>,
+    cap: usize,
+    ptr: NonNull<T>,
+    remaining: usize,
+    _marker: PhantomData<(T, Value<L>)>
+}
+
+impl<T, L: Term> Drop for DVecIntoIter<T, L> {
+    fn drop(&mut self) {
+        unsafe {
+            core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(self.ptr.as_ptr(), self.remaining));
+            drop(Vec::from_raw_parts(self.buf.as_ptr(), 0, self.cap));
+        }
+    }
+}
+
+// SAFETY: the iterator owns its elements and buffer, like std's vec::IntoIter
+unsafe impl<T: Send, L: Term> Send for DVecIntoIter<T, L> {}
+
+// SAFETY: shared references to the iterator give no access to the elements
+unsafe impl<T: Sync, L: Term> Sync for DVecIntoIter<T, L> {}
+
+pub struct DVecIntoIterFamily<T, LT>(PhantomData<fn(LT) -> T>);
+
+unsafe impl<T, LT: UInt> DLIterFamily for DVecIntoIterFamily<T, LT> {
+    type LengthType = LT;
+    type Iter<L: Term<Type = Self::LengthType>> = DVecIntoIter<T, L>;
+    type Item = T;
+
+    fn next<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        _value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        // SAFETY: the length is a successor, so there is at least one element left
+        let x = unsafe {iter.ptr.as_ptr().read()};
+        let next = DVecIntoIter {
+            buf: iter.buf,
+            cap: iter.cap,
+            ptr: unsafe {NonNull::new_unchecked(iter.ptr.as_ptr().add(1))},
+            remaining: iter.remaining - 1,
+            _marker: PhantomData
+        };
+        core::mem::forget(iter);
+        (next, x)
+    }
+
+    fn finish(_iter: Self::Iter<Zero<Self::LengthType>>) {
+    }
+}
+
+impl<T, LT: UInt> DLIterFamilyBack for DVecIntoIterFamily<T, LT> {
+    fn next_back<L: Term<Type = Self::LengthType>>(
+        iter: Self::Iter<Succ<L>>,
+        _value: Value<L>
+    ) -> (Self::Iter<L>, Self::Item) {
+        // SAFETY: the length is a successor, so there is at least one element left
+        let x = unsafe {iter.ptr.as_ptr().add(iter.remaining - 1).read()};
+        let next = DVecIntoIter {
+            buf: iter.buf,
+            cap: iter.cap,
+            ptr: iter.ptr,
+            remaining: iter.remaining - 1,
+            _marker: PhantomData
+        };
+        core::mem::forget(iter);
+        (next, x)
+    }
+}
+
+impl<T, L: Term> DLIter for DVecIntoIter<T, L>
+    where L::Type: UInt {
+    type LengthType = L::Type;
+    type Length = L;
+    type Family = DVecIntoIterFamily<T, L::Type>;
+
+    fn drop(self, _len: Value<L>) {
+    }
+}
+
+/// Family of DVec types with varying length, for use with DPair
+pub struct DVecFamily<T>(PhantomData<T>);
+
+unsafe impl<T, LT> Term2S<LT> for DVecFamily<T>
+    where usize: From<LT>, LT: UInt {
+    type Type<L: Term<Type = LT>> = DVec<T, L>;
 }
 
 impl<T, L: Term> From<DVec<T, L>> for DLVec<T, L>
@@ -193,14 +445,103 @@ impl<T, L: Term> From<DVec<T, L>> for DLVec<T, L>
 
 impl<T, L: Term> Deref for DVec<T, L>
     where usize: From<L::Type>, L::Type: UInt {
-    type Target = DLVec<T, L>;
+    type Target = DSlice<T, L>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        self.0.dslice(self.len())
     }
 }
 
 impl<T, L: Term> DerefMut for DVec<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        let len = self.len();
+        self.0.dslice_mut(len)
+    }
+}
+
+/// A DVec whose capacity is at least C, allowing pushes that are guaranteed not to reallocate
+///
+/// C is the actual capacity of the buffer if it fits in the length type, or else the requested capacity
+#[repr(C)]
+pub struct DCapVec<T, L: Term, C: Term<Type = L::Type>>(DVec<T, L>, Value<C>)
+    where usize: From<L::Type>, L::Type: UInt;
+
+impl<T, L: Term, C: Term<Type = L::Type>> DCapVec<T, L, C>
+    where usize: From<L::Type>, L::Type: UInt {
+    pub fn len(&self) -> Value<L> {
+        self.0.len()
+    }
+
+    pub fn capacity(&self) -> Value<C> {
+        self.1.clone()
+    }
+
+    pub fn into_dvec(self) -> DVec<T, L> {
+        self.0
+    }
+
+    /// Push an element without allocating, since the length is less than the capacity
+    pub fn push_within_capacity(self, x: T, _lt: ValueLt<L, C>) -> DCapVec<T, Succ<L>, C> {
+        let DCapVec(v, cap) = self;
+        let len = v.len();
+        let (ptr, c) = (v.0.0, v.0.1);
+        core::mem::forget(v);
+        // SAFETY: the buffer has room for at least C > L elements
+        unsafe {ptr.add(usize::from(len.clone().into_inner())).write(x)};
+        DCapVec(DVec(ManuallyDrop::new(DLVec(ptr, c, PhantomData)), Succ(len)), cap)
+    }
+}
+
+impl<T, L: Term, C: Term<Type = L::Type>> DCapVec<T, Succ<L>, C>
+    where usize: From<L::Type>, L::Type: UInt {
+    pub fn pop(self) -> (DCapVec<T, L, C>, T) {
+        let DCapVec(v, cap) = self;
+        let (v, x) = v.pop();
+        (DCapVec(v, cap), x)
+    }
+}
+
+impl<'a, T, LT> DCapVec<T, Zero<LT>, Var<'a, LT>>
+    where usize: From<LT>, LT: UInt + TryFrom<usize> {
+    pub fn with_capacity(guard: Guard<'a>, cap: LT) -> (Self, Value<Var<'a, LT>>) {
+        let v = DVec::new_unchecked_with_capacity(guard, Vec::with_capacity(usize::from(cap.clone())), Zero(), cap);
+        let cap = v.capacity();
+        (v, cap)
+    }
+}
+
+impl<T, L: Term> DVec<T, L>
+    where usize: From<L::Type>, L::Type: UInt + TryFrom<usize> {
+    /// Reserve space for at least `additional` more elements, returning a DCapVec and its capacity
+    pub fn reserve<'a>(self, guard: Guard<'a>, additional: L::Type) -> (DCapVec<T, L, Erasure<'a, L>>, Value<Erasure<'a, L>>) {
+        let len = self.len();
+        let requested = num_traits::CheckedAdd::checked_add(&len.clone().into_inner(), &additional).expect("DVec::reserve() overflowed");
+        let mut v = self.into_vec();
+        v.reserve(usize::from(additional));
+        let v = DVec::new_unchecked_with_capacity(guard, v, len, requested);
+        let cap = v.capacity();
+        (v, cap)
+    }
+
+    /// requested must be less or equal than the capacity of vec
+    fn new_unchecked_with_capacity(guard: Guard<'_>, vec: Vec<T>, len: Value<L>, requested: L::Type) -> DCapVec<T, L, Var<'_, L::Type>> {
+        let cap = Var(guard, L::Type::try_from(vec.capacity()).unwrap_or(requested));
+        DCapVec(unsafe {DVec::new_unchecked(vec, len)}, cap)
+    }
+}
+
+impl<T, L: Term, C: Term<Type = L::Type>> Deref for DCapVec<T, L, C>
+    where usize: From<L::Type>, L::Type: UInt {
+    type Target = DSlice<T, L>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Only derefs mutably to the DSlice, since replacing the DVec could replace the buffer with a smaller one
+impl<T, L: Term, C: Term<Type = L::Type>> DerefMut for DCapVec<T, L, C>
     where usize: From<L::Type>, L::Type: UInt {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
@@ -244,3 +585,187 @@ pub fn test_fin_range() {
         println!("{}: {}", i, c[i]);
     }
 }
+
+#[test]
+pub fn test_length_changing_ops() {
+    use alloc::vec;
+    use crate::term::value_le;
+
+    make_guard!(al);
+    let a = DVec::from(al, vec![1, 2, 3, 4]);
+    let idx = Fin::from(a.len(), 1).unwrap();
+    let (a, x) = a.remove(idx);
+    assert_eq!(x, 2);
+    let idx = Fin::from(Succ(a.len()), 0).unwrap();
+    let a = a.insert(idx, 0);
+    let a = a.push(2);
+    let (a, x) = a.pop();
+    assert_eq!(x, 2);
+    assert_eq!(a.len().into_inner(), 4);
+
+    make_guard!(ml);
+    let m = Var(ml, 1);
+    let le = value_le(m, a.len()).unwrap();
+    let (a, b) = a.split_off(m, le);
+    assert_eq!(a.len().into_inner(), 1);
+    assert_eq!(b.len().into_inner(), 3);
+    let c = b.append(a);
+    assert_eq!(c.into_vec(), vec![1, 3, 4, 0]);
+
+    make_guard!(dl);
+    let d = DVec::from(dl, vec![1, 1, 2, 2, 2, 3, 1]);
+    make_guard!(g);
+    let (len, d) = d.dedup().into_inner(g);
+    assert_eq!(len.into_inner(), 4);
+    make_guard!(g);
+    let (len, d) = d.retain(|x| *x != 1).into_inner(g);
+    assert_eq!(len.into_inner(), 2);
+    let le = value_le(Zero(), d.len()).unwrap();
+    let d = d.truncate(Zero(), le);
+    assert_eq!(d.into_vec(), vec![]);
+}
+
+#[test]
+pub fn test_map_zip() {
+    use alloc::vec;
+    use alloc::string::{String, ToString};
+
+    make_guard!(al);
+    let a = DVec::from(al, vec![1u32, 2, 3]);
+    let b = a.map(|x| x as i32 * 10);
+    let c: DVec<String, _> = b.map_indexed(|i, x| alloc::format!("{}:{}", i, x));
+    let d = c[Fin::from(c.len(), 2).unwrap()].clone();
+    assert_eq!(d, "2:30");
+    let lens = c.map_to_dvec(c.len(), |s| s.len());
+    let (c, lens) = c.zip(lens).unzip();
+    assert_eq!(lens.into_vec(), vec![4, 4, 4]);
+    assert_eq!(c.map(|s| s.to_string() + "!").into_vec(), vec!["0:10!", "1:20!", "2:30!"]);
+}
+
+#[test]
+pub fn test_map_panic() {
+    extern crate std;
+    use alloc::rc::Rc;
+    use alloc::vec;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let x = Rc::new(0);
+    make_guard!(al);
+    let a = DVec::from(al, vec![x.clone(), x.clone(), x.clone()]);
+    // Rc<i32> and Rc<u8> have the same layout, so the buffer is reused
+    let mut n = 0;
+    let r = catch_unwind(AssertUnwindSafe(|| a.map(|_| {
+        n += 1;
+        assert!(n < 2, "map panic");
+        Rc::new(0u8)
+    })));
+    assert!(r.is_err());
+    assert_eq!(Rc::strong_count(&x), 1);
+}
+
+#[test]
+pub fn test_dslice_ref() {
+    use alloc::vec;
+
+    make_guard!(al);
+    let mut a = DVec::from(al, vec![1, 2, 3]);
+    for x in a.as_dslice_mut().as_mut_slice() {
+        *x *= 2;
+    }
+    let r = a.as_dslice();
+    let (_, x) = r.iter().next().ok().unwrap();
+    assert_eq!(*x, 2);
+    assert_eq!(alloc::format!("{:?}", r), "[2, 4, 6]");
+    assert_eq!(r.as_slice(), &[2, 4, 6]);
+    assert_eq!(r.to_dvec().into_vec(), a.into_vec());
+}
+
+#[test]
+pub fn test_push_within_capacity() {
+    use crate::term::value_lt;
+
+    make_guard!(g);
+    let (v, cap) = DCapVec::<u8, _, _>::with_capacity(g, 2usize);
+    let ptr = v.0.0.0;
+    let lt = value_lt(v.len(), cap).unwrap();
+    let v = v.push_within_capacity(1, lt);
+    let lt = value_lt(v.len(), cap).unwrap();
+    let v = v.push_within_capacity(2, lt);
+    assert_eq!(v.0.0.0, ptr);
+    let (v, x) = v.pop();
+    assert_eq!(x, 2);
+
+    make_guard!(g);
+    let (v, cap) = v.into_dvec().reserve(g, 10);
+    assert!(cap.into_inner() >= 11);
+    assert_eq!(v[Fin::from(v.len(), 0).unwrap()], 1);
+}
+
+#[test]
+pub fn test_into_iter_drop() {
+    use alloc::rc::Rc;
+    use alloc::vec;
+
+    let x = Rc::new(());
+    make_guard!(al);
+    let a = DVec::from(al, vec![x.clone(), x.clone(), x.clone()]);
+    let len = a.len();
+    let (iter, y) = match a.into_iter().next(len) {
+        Ok(r) => r,
+        Err(_) => unreachable!()
+    };
+    assert_eq!(Rc::strong_count(&x), 4);
+    drop(y);
+    iter.drop(Pred(len));
+    assert_eq!(Rc::strong_count(&x), 1);
+}
+
+#[test]
+pub fn test_into_iter_zst() {
+    use alloc::vec;
+
+    fn is_send<T: Send>(_: &T) {}
+
+    make_guard!(al);
+    let a = DVec::from(al, vec![(), (), ()]);
+    let len = a.len();
+    let iter = a.into_iter();
+    is_send(&iter);
+    let (iter, ()) = iter.next(len).ok().unwrap();
+    let (iter, ()) = iter.next(Pred(len)).ok().unwrap();
+    iter.drop(Pred(Pred(len)));
+}
+
+#[test]
+pub fn test_into_iter_rev() {
+    use alloc::vec;
+    use crate::iter::DIter;
+
+    make_guard!(al);
+    let a = DVec::from(al, vec![1, 2, 3]);
+    let len = a.len();
+    let it = DIter::new(a.into_iter(), len).rev();
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(x, 3);
+    let (it, x) = it.next_back().ok().unwrap();
+    assert_eq!(x, 1);
+    let (it, x) = it.next().ok().unwrap();
+    assert_eq!(x, 2);
+    assert!(it.next().is_err());
+}
+
+#[test]
+pub fn test_collect_d() {
+    use crate::boxed::DBox;
+    use crate::int::ConstUsize;
+    use crate::iter::DIter;
+
+    let a = [1, 2, 3];
+    let n = ConstUsize::<3>();
+    let sa: &DSlice<i32, _> = (&a).into();
+    let v: DVec<i32, _> = sa.iter().map(|x| x * 2).collect_d(n);
+    assert_eq!(v.as_slice(), &[2, 4, 6]);
+    assert_eq!(v.into_vec().capacity(), 3);
+    let b: DBox<i32, _> = DIter::new(sa.iter(), n).map(|x| x + 1).collect_d();
+    assert_eq!(&*b.into_inner().0, &[2, 3, 4]);
+}
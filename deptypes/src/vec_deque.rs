@@ -0,0 +1,202 @@
+use core::marker::PhantomData;
+use core::ops::{Index, IndexMut};
+
+use crate::fin::Fin;
+use crate::int::sub::s_a_minus_s_0_eq_a;
+use crate::int::uint::UInt;
+use crate::int::{Pred, Succ, Zero};
+use crate::iter::{DLIter, DLIterFamily};
+use crate::term::{Term, Value, ValueEq};
+use crate::transmutable::{coerce, Equiv};
+use crate::vec::DVec;
+
+use alloc::collections::VecDeque;
+
+/// Double-ended queue whose length is L
+#[repr(C)]
+pub struct DVecDeque<T, L: Term>(VecDeque<T>, Value<L>);
+
+impl<T, LT> DVecDeque<T, Zero<LT>>
+    where usize: From<LT>, LT: UInt {
+    pub fn new() -> Self {
+        DVecDeque(VecDeque::new(), Zero())
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        DVecDeque(VecDeque::with_capacity(cap), Zero())
+    }
+}
+
+impl<T, LT> Default for DVecDeque<T, Zero<LT>>
+    where usize: From<LT>, LT: UInt {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, L: Term> DVecDeque<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    pub fn equiv<L1: Term>(_: ValueEq<L, L1>) -> Equiv<DVecDeque<T, L>, DVecDeque<T, L1>>
+        where usize: From<L1::Type>, L1::Type: UInt {
+        unsafe {Equiv::axiom()}
+    }
+
+    /// # Safety
+    ///
+    /// deque must have length len
+    pub unsafe fn new_unchecked(deque: VecDeque<T>, len: Value<L>) -> Self {
+        DVecDeque(deque, len)
+    }
+
+    pub fn into_vec_deque(self) -> VecDeque<T> {
+        self.0
+    }
+
+    pub fn len(&self) -> Value<L> {
+        self.1.clone()
+    }
+
+    pub fn push_back(self, x: T) -> DVecDeque<T, Succ<L>> {
+        let DVecDeque(mut d, len) = self;
+        d.push_back(x);
+        DVecDeque(d, Succ(len))
+    }
+
+    pub fn push_front(self, x: T) -> DVecDeque<T, Succ<L>> {
+        let DVecDeque(mut d, len) = self;
+        d.push_front(x);
+        DVecDeque(d, Succ(len))
+    }
+
+    /// Iterate from the front to the back
+    pub fn into_diter(self) -> DVecDequeIntoIter<T, L> {
+        DVecDequeIntoIter(self.0, PhantomData)
+    }
+
+    /// Iterate from the back to the front
+    pub fn into_rev_diter(self) -> DVecDequeIntoRevIter<T, L> {
+        DVecDequeIntoRevIter(self.0, PhantomData)
+    }
+}
+
+impl<T, L: Term> DVecDeque<T, Succ<L>>
+    where usize: From<L::Type>, L::Type: UInt {
+    pub fn pop_front(self) -> (DVecDeque<T, L>, T) {
+        let DVecDeque(mut d, len) = self;
+        // SAFETY: the length is a successor and thus not zero
+        let x = unsafe {d.pop_front().unwrap_unchecked()};
+        (DVecDeque(d, coerce(Pred(len), s_a_minus_s_0_eq_a())), x)
+    }
+
+    pub fn pop_back(self) -> (DVecDeque<T, L>, T) {
+        let DVecDeque(mut d, len) = self;
+        // SAFETY: the length is a successor and thus not zero
+        let x = unsafe {d.pop_back().unwrap_unchecked()};
+        (DVecDeque(d, coerce(Pred(len), s_a_minus_s_0_eq_a())), x)
+    }
+}
+
+impl<T, L: Term> Index<Fin<L>> for DVecDeque<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    type Output = T;
+
+    fn index(&self, index: Fin<L>) -> &Self::Output {
+        unsafe {self.0.get(usize::from(index.into_inner())).unwrap_unchecked()}
+    }
+}
+
+impl<T, L: Term> IndexMut<Fin<L>> for DVecDeque<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    fn index_mut(&mut self, index: Fin<L>) -> &mut Self::Output {
+        unsafe {self.0.get_mut(usize::from(index.into_inner())).unwrap_unchecked()}
+    }
+}
+
+impl<T, L: Term> From<DVec<T, L>> for DVecDeque<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    fn from(x: DVec<T, L>) -> Self {
+        let len = x.len();
+        DVecDeque(VecDeque::from(x.into_vec()), len)
+    }
+}
+
+impl<T, L: Term> From<DVecDeque<T, L>> for DVec<T, L>
+    where usize: From<L::Type>, L::Type: UInt {
+    fn from(x: DVecDeque<T, L>) -> Self {
+        let DVecDeque(d, len) = x;
+        unsafe {DVec::new_unchecked(d.into(), len)}
+    }
+}
+
+macro_rules! impl_deque_iter {
+    ($(#[$m:meta])* $S:ident $F:ident $pop:ident) => {
+        $(#[$m])*
+        #[repr(C)]
+        pub struct $S<T, L: Term>(VecDeque<T>, PhantomData<Value<L>>);
+
+        pub struct $F<T, LT>(PhantomData<fn(LT) -> T>);
+
+        unsafe impl<T, LT: UInt> DLIterFamily for $F<T, LT> {
+            type LengthType = LT;
+            type Iter<L: Term<Type = Self::LengthType>> = $S<T, L>;
+            type Item = T;
+
+            fn next<L: Term<Type = Self::LengthType>>(
+                iter: Self::Iter<Succ<L>>,
+                _value: Value<L>
+            ) -> (Self::Iter<L>, Self::Item) {
+                let mut d = iter.0;
+                // SAFETY: the length is a successor and thus not zero
+                let x = unsafe {d.$pop().unwrap_unchecked()};
+                ($S(d, PhantomData), x)
+            }
+
+            fn finish(_iter: Self::Iter<Zero<Self::LengthType>>) {
+            }
+        }
+
+        impl<T, L: Term> DLIter for $S<T, L>
+            where L::Type: UInt {
+            type LengthType = L::Type;
+            type Length = L;
+            type Family = $F<T, L::Type>;
+
+            fn drop(self, _len: Value<L>) {
+            }
+        }
+    }
+}
+
+impl_deque_iter! {
+    /// Owning iterator over a DVecDeque, from the front to the back
+    DVecDequeIntoIter DVecDequeIntoIterFamily pop_front
+}
+
+impl_deque_iter! {
+    /// Owning iterator over a DVecDeque, from the back to the front
+    DVecDequeIntoRevIter DVecDequeIntoRevIterFamily pop_back
+}
+
+#[test]
+pub fn test_vec_deque() {
+    use alloc::vec;
+    use crate::guard::make_guard;
+
+    make_guard!(g);
+    let d = DVecDeque::from(DVec::from(g, vec![2, 3]));
+    let d = d.push_front(1).push_back(4);
+    assert_eq!(d[Fin::from(d.len(), 0).unwrap()], 1);
+    let (d, x) = d.pop_back();
+    assert_eq!(x, 4);
+    let (d, x) = d.pop_front();
+    assert_eq!(x, 1);
+    let len = d.len();
+    let r = DVec::new().add_iter(d.into_rev_diter(), len);
+    assert_eq!(r.into_vec(), vec![3, 2]);
+
+    make_guard!(g);
+    let d = DVecDeque::from(DVec::from(g, vec![2, 3])).push_front(1);
+    let len = d.len();
+    let r = DVec::new().add_iter(d.into_diter(), len);
+    assert_eq!(r.into_vec(), vec![1, 2, 3]);
+}